@@ -1,11 +1,32 @@
-use std::{cmp, env, fs};
-
+use std::{
+    cmp,
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
+    fs,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, Instant}
+};
+use chrono::{Datelike, Local, NaiveDate, TimeDelta};
+use tui::layout::Rect;
+
+use crate::commands::*;
+use crate::config::*;
 use crate::inputs::*;
+use crate::keymap::*;
 use crate::lists::*;
+use crate::stats::*;
 
 const TRACKER_FILE: &str = "tracker.json";
 const BACKLOG_FILE: &str = "backlog.json";
 const ARCHIVE_FILE: &str = "archive.json";
+const HISTORY_DIR: &str = "history";
+const LOCK_FILE: &str = ".lock";
+
+pub const MIN_LIST_WIDTH: u16 = 20;
+
+pub const AUTO_ARCHIVE_INTERVAL: Duration = Duration::from_secs(60);
+pub const TIMER_TICK: Duration = Duration::from_secs(1);
 
 #[derive(Clone)]
 pub enum AppState {
@@ -17,62 +38,211 @@ pub enum AppState {
     TaskView(Box<AppState>),
     BacklogPopup(Box<AppState>),
     ArchivePopup(Box<AppState>),
+    ArchiveSearch(Box<AppState>),
     EditTask(Box<AppState>),
     CreateTask(Box<AppState>),
     DeleteTask(Box<AppState>),
+    SetCategory(Box<AppState>),
+    SendToProject(Box<AppState>),
+    SendToProjectList(Box<AppState>),
+    MoveToList(Box<AppState>),
+    CommandLine(Box<AppState>),
     EditList(Box<AppState>),
     CreateList(Box<AppState>),
     DeleteList(Box<AppState>),
+    ConfirmMove(Box<AppState>),
+    Statistics(Box<AppState>),
+    FlowCharts(Box<AppState>),
+    SetBurndownTarget(Box<AppState>),
+    Help(Box<AppState>),
+    HelpSearch(Box<AppState>),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ListRef {
+    Tracker(usize),
+    Backlog,
+    Archive,
+}
+
+// Where a task in the register came from, which still holds after switching
+// projects or rearranging lists
+#[derive(Clone, PartialEq, Eq)]
+pub struct ListOrigin {
+    pub project: String,
+    pub list: ListRef,
+    pub name: String,
+}
+
+#[derive(Clone, Copy)]
+pub enum TaskMove {
+    // Each moves a task the given number of lists along from its own
+    NextList(usize),
+    PrevList(usize),
+    ToList(usize),
+    Backlog,
+    Archive,
+    Restore,
+    Drop((usize, usize), (usize, usize)),
+    Paste(bool, usize),
+}
+
+#[derive(Clone)]
+pub enum Change {
+    TaskDown(usize),
+    TaskUp(usize),
+    TaskToTop,
+    TaskToBottom,
+    Move(TaskMove),
+    Paste(bool, usize),
+    Category(String),
+    Delete,
+}
+
+pub struct Completion {
+    stem: String,
+    candidates: Vec<String>,
+    index: usize,
 }
 
 pub struct App {
+    pub config: Config,
+
     pub project_title: String,
     pub project_list: ProjectList,
     pub project_detail_input: Input,
 
     pub unsaved_changes: bool,
+    pub discard_changes: bool,
     pub quit: bool,
     pub state: AppState,
+    pub message: Option<String>,
 
     pub task_lists: Vec<TaskList>,
     pub active_list: usize,
+    pub list_offset: usize,
+    pub visible_lists: usize,
+
+    pub filter: Option<TaskFilter>,
+    pub swimlanes: bool,
+    pub active_lane: usize,
+    pub lane_offsets: HashMap<(usize, usize), usize>,
 
     pub backlog: TaskList,
     pub archive: TaskList,
+    pub last_auto_archive: Instant,
+    pub loaded_archive_years: Vec<i32>,
+    pub archive_grouping: ArchiveGrouping,
+    pub archive_search_input: Input,
+
+    pub burndown_target: Option<NaiveDate>,
+    pub burndown_target_input: Input,
 
     pub detail_scroll: u16,
 
+    pub list_areas: Vec<(usize, Rect)>,
+    pub lane_areas: Vec<(usize, Rect)>,
+    pub card_areas: Vec<(usize, usize, Rect)>,
+    pub popup_card_areas: Vec<(usize, Rect)>,
+    pub drag: Option<(usize, usize)>,
+    pub drag_over: Option<usize>,
+    pub pending_move: Option<TaskMove>,
+    pub count: Option<usize>,
+    pub last_change: Option<Change>,
+    pub last_click: Option<(Instant, u16, u16)>,
+
     pub task_detail_inputs: Vec<Input>,
     pub active_detail_input: usize,
+    pub category_input: Input,
+
+    pub mark_anchor: Option<(ListRef, usize)>,
+    pub register: Vec<(ListOrigin, Task)>,
+
+    pub picker: Picker,
+    pub send_project: String,
+    pub send_copy: bool,
 
-    pub list_detail_input: Input,
+    pub list_detail_inputs: Vec<Input>,
+
+    pub command_input: Input,
+    pub completion: Option<Completion>,
+
+    pub help_scroll: u16,
+    pub help_search_input: Input,
 }
 
 impl App {
-    pub fn create(project_title: String) -> Result<Self, std::io::Error> {
+    pub fn create(project_title: String, config: Config) -> Result<Self, std::io::Error> {
         let mut app = Self {
+            config,
+
             project_title,
             project_list: ProjectList::create()?,
             project_detail_input: Input::new()
                 .name("Project Name".to_string()),
 
             unsaved_changes: false,
+            discard_changes: false,
             quit: false,
             state: AppState::Tracker,
+            message: None,
 
             task_lists: Vec::new(),
             active_list: 0,
+            list_offset: 0,
+            visible_lists: 0,
+
+            filter: None,
+            swimlanes: false,
+            active_lane: 0,
+            lane_offsets: HashMap::new(),
 
             backlog: TaskList::empty_backlog(),
             archive: TaskList::empty_archive(),
+            last_auto_archive: Instant::now(),
+            loaded_archive_years: Vec::new(),
+            archive_grouping: ArchiveGrouping::Week,
+            archive_search_input: Input::new()
+                .name("Search".to_string()),
+
+            burndown_target: None,
+            burndown_target_input: Input::new()
+                .name("Target Date (YYYY-MM-DD)".to_string()),
 
             detail_scroll: 0,
 
+            list_areas: Vec::new(),
+            lane_areas: Vec::new(),
+            card_areas: Vec::new(),
+            popup_card_areas: Vec::new(),
+            drag: None,
+            drag_over: None,
+            pending_move: None,
+            count: None,
+            last_change: None,
+            last_click: None,
+
             task_detail_inputs: Vec::new(),
             active_detail_input: 0,
+            category_input: Input::new()
+                .name("Category".to_string()),
+
+            mark_anchor: None,
+            register: Vec::new(),
+
+            picker: Picker::new(),
+            send_project: String::new(),
+            send_copy: false,
 
-            list_detail_input: Input::new()
-                .name("List Name".to_string()),
+            list_detail_inputs: Vec::new(),
+
+            command_input: Input::new()
+                .name("Command".to_string()),
+            completion: None,
+
+            help_scroll: 0,
+            help_search_input: Input::new()
+                .name("Search".to_string()),
         };
 
         app.task_detail_inputs.push(
@@ -87,14 +257,40 @@ impl App {
             Input::new()
             .name("Category".to_string())
         );
+        app.task_detail_inputs.push(
+            Input::new()
+            .name("Due (YYYY-MM-DD)".to_string())
+        );
+        app.task_detail_inputs.push(
+            Input::new()
+            .name("Priority".to_string())
+        );
+        app.task_detail_inputs.push(
+            Input::new()
+            .name(app.estimate_input_name())
+        );
+
+        app.list_detail_inputs.push(
+            Input::new()
+            .name("List Name".to_string())
+        );
+        app.list_detail_inputs.push(
+            Input::new()
+            .name("WIP Limit".to_string())
+        );
+        app.list_detail_inputs.push(
+            Input::new()
+            .name("Archive After (Days)".to_string())
+        );
 
-        if app.project_title == "" {
+        if app.project_title.is_empty() {
             app.state = AppState::ProjectMenu;
         } else {
             app.read_project_data(app.project_title.clone())?;
         }
 
         app.reset_list_selections();
+        app.auto_archive();
 
         Ok(app)
     }
@@ -103,10 +299,15 @@ impl App {
         let mut path = env::current_dir()?;
         path.push(project_title);
         env::set_current_dir(&path)?;
+        // Marks the project as open, so that pruning its archive from the
+        // command line waits until kadai is done with it
+        fs::write(path.join(LOCK_FILE), process::id().to_string())?;
 
-        self.task_lists = read_tracker_file()?;
-        self.backlog = read_backlog_file()?;
-        self.archive = read_archive_file()?;
+        self.task_lists = read_tracker_file(&path)?;
+        self.backlog = read_backlog_file(&path)?;
+        self.archive = read_archive_file(&path)?;
+        self.loaded_archive_years.clear();
+        self.burndown_target = None;
 
         Ok(())
     }
@@ -130,12 +331,18 @@ impl App {
             self.project_title = project.clone();
             self.read_project_data(project)?;
             self.reset_list_selections();
+            self.auto_archive();
+            self.active_list = 0;
+            self.list_offset = 0;
+            self.mark_anchor = None;
+            self.filter = None;
         }
 
         Ok(())
     }
 
     pub fn move_up_one_directory(&mut self) -> Result<(), std::io::Error> {
+        self.close_project()?;
         self.project_title = String::new();
         let mut path = env::current_dir()?;
         path.pop();
@@ -144,17 +351,40 @@ impl App {
         Ok(())
     }
 
-    pub fn get_highlighted_project(&self) -> Option<String> {
-        match self.project_list.get_selected_index() {
-            Some(i) => Some(self.project_list.projects[i].clone()),
-            None => None
+    pub fn close_project(&self) -> Result<(), std::io::Error> {
+        let path = env::current_dir()?.join(LOCK_FILE);
+        if !self.project_title.is_empty() && path.exists() {
+            fs::remove_file(path)?;
         }
+
+        Ok(())
+    }
+
+    pub fn get_highlighted_project(&self) -> Option<String> {
+        self.project_list.get_selected_index().map(|i| self.project_list.projects[i].clone())
     }
 
     pub fn set_quit(&mut self, quit: bool) {
         self.quit = quit;
     }
 
+    fn estimate_input_name(&self) -> String {
+        format!("Estimate ({})", self.config.estimate_unit.name())
+    }
+
+    pub fn tracked_estimate(&self) -> Option<f64> {
+        let estimates: Vec<f64> = self.task_lists
+            .iter()
+            .filter_map(|list| list.total_estimate())
+            .collect();
+
+        if estimates.is_empty() {
+            None
+        } else {
+            Some(estimates.iter().sum())
+        }
+    }
+
     pub fn num_tracked_tasks(&self) -> usize {
         let sum: usize = self.task_lists
             .iter()
@@ -169,13 +399,25 @@ impl App {
             AppState::Tracker => &self.task_lists[self.active_list],
             AppState::BacklogPopup(_) => &self.backlog,
             AppState::ArchivePopup(_) => &self.archive,
-            AppState::TaskView(prev) => self.get_focused_list(&*prev),
-            AppState::EditTask(prev) => self.get_focused_list(&*prev),
-            AppState::CreateTask(prev) => self.get_focused_list(&*prev),
-            AppState::DeleteTask(prev) => self.get_focused_list(&*prev),
-            AppState::EditList(prev) => self.get_focused_list(&*prev),
-            AppState::CreateList(prev) => self.get_focused_list(&*prev),
-            AppState::DeleteList(prev) => self.get_focused_list(&*prev),
+            AppState::ArchiveSearch(prev) => self.get_focused_list(prev),
+            AppState::TaskView(prev) => self.get_focused_list(prev),
+            AppState::EditTask(prev) => self.get_focused_list(prev),
+            AppState::CreateTask(prev) => self.get_focused_list(prev),
+            AppState::DeleteTask(prev) => self.get_focused_list(prev),
+            AppState::SetCategory(prev) => self.get_focused_list(prev),
+            AppState::SendToProject(prev) => self.get_focused_list(prev),
+            AppState::SendToProjectList(prev) => self.get_focused_list(prev),
+            AppState::MoveToList(prev) => self.get_focused_list(prev),
+            AppState::CommandLine(prev) => self.get_focused_list(prev),
+            AppState::EditList(prev) => self.get_focused_list(prev),
+            AppState::CreateList(prev) => self.get_focused_list(prev),
+            AppState::DeleteList(prev) => self.get_focused_list(prev),
+            AppState::ConfirmMove(prev) => self.get_focused_list(prev),
+            AppState::Statistics(prev) => self.get_focused_list(prev),
+            AppState::FlowCharts(prev) => self.get_focused_list(prev),
+            AppState::SetBurndownTarget(prev) => self.get_focused_list(prev),
+            AppState::Help(prev) => self.get_focused_list(prev),
+            AppState::HelpSearch(prev) => self.get_focused_list(prev),
             _ => unreachable!()
         }
     }
@@ -186,29 +428,25 @@ impl App {
             AppState::Tracker => &mut self.task_lists[self.active_list],
             AppState::BacklogPopup(_) => &mut self.backlog,
             AppState::ArchivePopup(_) => &mut self.archive,
-            AppState::TaskView(prev) => self.get_mut_focused_list(&*prev),
-            AppState::EditTask(prev) => self.get_mut_focused_list(&*prev),
-            AppState::CreateTask(prev) => self.get_mut_focused_list(&*prev),
-            AppState::DeleteTask(prev) => self.get_mut_focused_list(&*prev),
-            AppState::EditList(prev) => self.get_mut_focused_list(&*prev),
-            AppState::CreateList(prev) => self.get_mut_focused_list(&*prev),
-            AppState::DeleteList(prev) => self.get_mut_focused_list(&*prev),
-            _ => unreachable!()
-        }
-    }
-
-    fn set_focused_list(&mut self, state: &AppState, list: TaskList) {
-        match state {
-            AppState::Tracker => self.task_lists[self.active_list] = list,
-            AppState::BacklogPopup(_) => self.backlog = list,
-            AppState::ArchivePopup(_) => self.archive = list,
-            AppState::TaskView(prev) => self.set_focused_list(&*prev, list),
-            AppState::EditTask(prev) => self.set_focused_list(&*prev, list),
-            AppState::CreateTask(prev) => self.set_focused_list(&*prev, list),
-            AppState::DeleteTask(prev) => self.set_focused_list(&*prev, list),
-            AppState::EditList(prev) => self.set_focused_list(&*prev, list),
-            AppState::CreateList(prev) => self.set_focused_list(&*prev, list),
-            AppState::DeleteList(prev) => self.set_focused_list(&*prev, list),
+            AppState::ArchiveSearch(prev) => self.get_mut_focused_list(prev),
+            AppState::TaskView(prev) => self.get_mut_focused_list(prev),
+            AppState::EditTask(prev) => self.get_mut_focused_list(prev),
+            AppState::CreateTask(prev) => self.get_mut_focused_list(prev),
+            AppState::DeleteTask(prev) => self.get_mut_focused_list(prev),
+            AppState::SetCategory(prev) => self.get_mut_focused_list(prev),
+            AppState::SendToProject(prev) => self.get_mut_focused_list(prev),
+            AppState::SendToProjectList(prev) => self.get_mut_focused_list(prev),
+            AppState::MoveToList(prev) => self.get_mut_focused_list(prev),
+            AppState::CommandLine(prev) => self.get_mut_focused_list(prev),
+            AppState::EditList(prev) => self.get_mut_focused_list(prev),
+            AppState::CreateList(prev) => self.get_mut_focused_list(prev),
+            AppState::DeleteList(prev) => self.get_mut_focused_list(prev),
+            AppState::ConfirmMove(prev) => self.get_mut_focused_list(prev),
+            AppState::Statistics(prev) => self.get_mut_focused_list(prev),
+            AppState::FlowCharts(prev) => self.get_mut_focused_list(prev),
+            AppState::SetBurndownTarget(prev) => self.get_mut_focused_list(prev),
+            AppState::Help(prev) => self.get_mut_focused_list(prev),
+            AppState::HelpSearch(prev) => self.get_mut_focused_list(prev),
             _ => unreachable!()
         }
     }
@@ -231,12 +469,13 @@ impl App {
                 }
             },
             _ => {
+                let tasks = self.get_focusable_tasks();
                 let list = self.get_mut_focused_list(&self.state.clone());
 
-                if !list.is_empty() {
-                    let i = match list.get_selected_index() {
+                if !tasks.is_empty() {
+                    let i = match get_position(&tasks, list.get_selected_index()) {
                         Some(i) => {
-                            if i >= list.len() - 1 {
+                            if i >= tasks.len() - 1 {
                                 0
                             } else {
                                 i + 1
@@ -244,7 +483,7 @@ impl App {
                         },
                         None => 0,
                     };
-                    list.select(Some(i));
+                    list.select(Some(tasks[i]));
                 }
             }
         }
@@ -268,25 +507,341 @@ impl App {
                 }
             },
             _ => {
+                let tasks = self.get_focusable_tasks();
                 let list = self.get_mut_focused_list(&self.state.clone());
 
-                if !list.is_empty() {
-                    let i = match list.get_selected_index() {
+                if !tasks.is_empty() {
+                    let i = match get_position(&tasks, list.get_selected_index()) {
                         Some(i) => {
                             if i == 0 {
-                                list.len() - 1
+                                tasks.len() - 1
                             } else {
                                 i - 1
                             }
                         },
-                        None => cmp::max(list.len() - 1, 0),
+                        None => tasks.len() - 1,
                     };
-                    list.select(Some(i));
+                    list.select(Some(tasks[i]));
                 }
             }
         }
     }
 
+    pub fn update_list_viewport(&mut self, width: u16) {
+        let num_lists = self.task_lists.len();
+        self.list_offset = cmp::min(self.list_offset, num_lists - 1);
+
+        // Scroll just far enough to bring the active list into view
+        if self.active_list < self.list_offset {
+            self.list_offset = self.active_list;
+        }
+        while self.active_list >= self.list_offset + self.count_fitting_lists(self.list_offset, width) {
+            self.list_offset += 1;
+        }
+
+        // Don't leave empty space at the end if earlier lists would fit in it
+        while self.list_offset > 0
+            && self.count_fitting_lists(self.list_offset - 1, width) > num_lists - self.list_offset {
+            self.list_offset -= 1;
+        }
+
+        self.visible_lists = self.count_fitting_lists(self.list_offset, width);
+    }
+
+    fn count_fitting_lists(&self, start: usize, width: u16) -> usize {
+        // Collapsed lists only take up a narrow strip, and don't count
+        // towards the configured number of columns
+        let min_width = cmp::max(self.config.min_column_width, MIN_LIST_WIDTH);
+        let max_columns = self.config.columns.map(|columns| cmp::max(columns, 1));
+        let mut used_width = 0;
+        let mut columns = 0;
+        let mut count = 0;
+
+        for i in start..self.task_lists.len() {
+            let list_width = if self.is_list_collapsed(i) {
+                self.task_lists[i].collapsed_width()
+            } else {
+                columns += 1;
+                min_width
+            };
+
+            let too_many = max_columns.is_some_and(|max| columns > max);
+            if count > 0 && (used_width + list_width > width || too_many) {
+                break;
+            }
+
+            used_width += list_width;
+            count += 1;
+        }
+
+        count
+    }
+
+    pub fn is_list_collapsed(&self, index: usize) -> bool {
+        // The focused list is always shown in full, so collapsed lists can
+        // still be stepped through and worked on
+        self.task_lists[index].collapsed && index != self.active_list
+    }
+
+    pub fn toggle_list_collapse(&mut self) {
+        let list = &mut self.task_lists[self.active_list];
+        list.collapsed = !list.collapsed;
+
+        // Step off a freshly collapsed list so that it actually collapses
+        if list.collapsed && self.task_lists.len() > 1 {
+            if self.active_list + 1 < self.task_lists.len() {
+                self.next_list();
+            } else {
+                self.prev_list();
+            }
+        }
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn num_lists_hidden_left(&self) -> usize {
+        self.list_offset
+    }
+
+    pub fn num_lists_hidden_right(&self) -> usize {
+        self.task_lists.len() - self.list_offset - self.visible_lists
+    }
+
+    pub fn toggle_swimlanes(&mut self) {
+        self.swimlanes = !self.swimlanes;
+        self.active_lane = 0;
+        self.lane_offsets.clear();
+
+        if self.swimlanes {
+            self.sync_lane_focus();
+        } else {
+            self.restore_list_selections();
+        }
+    }
+
+    fn restore_list_selections(&mut self) {
+        // Lists may have lost their selection to an empty lane or filter
+        for list in self.task_lists.iter_mut() {
+            if list.get_selected_index().is_none() && !list.is_empty() {
+                list.select(Some(0));
+            }
+        }
+    }
+
+    pub fn set_filter(&mut self, filter: Option<TaskFilter>) {
+        self.filter = filter;
+        self.lane_offsets.clear();
+
+        if self.filter.is_none() {
+            self.restore_list_selections();
+        }
+        self.sync_lane_focus();
+    }
+
+    fn is_task_visible(&self, task: &Task) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter.matches(task))
+    }
+
+    pub fn get_visible_tasks(&self, list_index: usize) -> Vec<usize> {
+        self.task_lists[list_index].tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, task)| self.is_task_visible(task))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn get_lanes(&self) -> Vec<Option<String>> {
+        // Tasks are grouped into lanes by category, with uncategorized tasks
+        // in a lane of their own at the bottom
+        let mut lanes: Vec<Option<String>> = self.task_lists
+            .iter()
+            .flat_map(|list| list.tasks.iter())
+            .filter(|task| self.is_task_visible(task))
+            .map(|task| task.category.clone())
+            .collect();
+        lanes.sort_by_key(|lane| (lane.is_none(), lane.clone()));
+        lanes.dedup();
+
+        if lanes.is_empty() {
+            lanes.push(None);
+        }
+
+        lanes
+    }
+
+    pub fn get_active_lane(&self) -> usize {
+        cmp::min(self.active_lane, self.get_lanes().len() - 1)
+    }
+
+    pub fn get_lane_tasks(&self, list_index: usize, lane: usize) -> Vec<usize> {
+        let category = &self.get_lanes()[lane];
+        let list = &self.task_lists[list_index];
+
+        self.get_visible_tasks(list_index)
+            .into_iter()
+            .filter(|i| list.tasks[*i].category == *category)
+            .collect()
+    }
+
+    fn get_shown_tasks(&self, list_index: usize) -> Vec<usize> {
+        if self.swimlanes {
+            self.get_lane_tasks(list_index, self.get_active_lane())
+        } else {
+            self.get_visible_tasks(list_index)
+        }
+    }
+
+    fn get_focusable_tasks(&self) -> Vec<usize> {
+        match self.state {
+            AppState::Tracker => self.get_shown_tasks(self.active_list),
+            AppState::ArchivePopup(_) | AppState::ArchiveSearch(_) => self.get_archive_tasks(),
+            _ => (0..self.get_focused_list(&self.state).len()).collect()
+        }
+    }
+
+    pub fn get_archive_tasks(&self) -> Vec<usize> {
+        // The archive browser shows the most recently archived tasks first,
+        // leaving out any that don't match the search
+        let search = self.archive_search_input.text.to_lowercase();
+        let mut tasks: Vec<usize> = (0..self.archive.len())
+            .filter(|i| self.archive.tasks[*i].contains_text(&search))
+            .collect();
+        tasks.sort_by_key(|i| cmp::Reverse(self.archive.tasks[*i].archived_at));
+
+        tasks
+    }
+
+    pub fn sync_archive_focus(&mut self) {
+        let tasks = self.get_archive_tasks();
+        if get_position(&tasks, self.archive.get_selected_index()).is_none() {
+            self.archive.select(tasks.first().copied());
+        }
+    }
+
+    pub fn open_archive(&mut self, prev: AppState) {
+        self.archive_search_input.clear();
+        self.change_state(AppState::ArchivePopup(Box::new(prev)));
+        self.sync_archive_focus();
+    }
+
+    pub fn load_older_archive(&mut self) -> Result<(), std::io::Error> {
+        // Years rotated out of the archive come back one at a time, newest
+        // first, and are saved back to their own files
+        let dir = env::current_dir()?;
+        let year = get_archive_years(&dir)?
+            .into_iter()
+            .find(|year| !self.loaded_archive_years.contains(year));
+
+        let year = match year {
+            Some(year) => year,
+            None => {
+                self.message = Some("There are no older archives".to_string());
+                return Ok(());
+            }
+        };
+
+        let tasks = read_archive_year_file(&dir, year)?;
+        self.message = Some(format!("Loaded {} Archived in {}", count_tasks(tasks.len()), year));
+        for mut task in tasks {
+            task.archive_year = Some(year);
+            self.archive.push(task);
+        }
+        self.loaded_archive_years.push(year);
+        self.sync_archive_focus();
+
+        Ok(())
+    }
+
+    pub fn cycle_archive_grouping(&mut self) {
+        self.archive_grouping = self.archive_grouping.next();
+    }
+
+    pub fn sync_lane_focus(&mut self) {
+        // Keeps the focused task inside the active lane's cell, and out of
+        // the tasks hidden by the filter
+        if !self.swimlanes && self.filter.is_none() {
+            return;
+        }
+
+        let tasks = self.get_shown_tasks(self.active_list);
+        let list = &mut self.task_lists[self.active_list];
+
+        if get_position(&tasks, list.get_selected_index()).is_none() {
+            list.select(tasks.first().copied());
+        }
+    }
+
+    pub fn next_lane(&mut self) {
+        if self.swimlanes {
+            self.active_lane = (self.get_active_lane() + 1) % self.get_lanes().len();
+            self.sync_lane_focus();
+        }
+    }
+
+    pub fn prev_lane(&mut self) {
+        if self.swimlanes {
+            let lane = self.get_active_lane();
+            self.active_lane = match lane.checked_sub(1) {
+                Some(i) => i,
+                None => self.get_lanes().len() - 1
+            };
+            self.sync_lane_focus();
+        }
+    }
+
+    pub fn focus_lane(&mut self, lane: usize) {
+        if lane < self.get_lanes().len() {
+            self.active_lane = lane;
+            self.sync_lane_focus();
+        }
+    }
+
+    pub fn prefill_lane_category(&mut self) {
+        if self.swimlanes {
+            if let Some(category) = self.get_lanes()[self.get_active_lane()].clone() {
+                self.task_detail_inputs[2] = Input::from(category)
+                    .name("Category".to_string());
+            }
+        }
+    }
+
+    pub fn page_down(&mut self) {
+        let amount = self.get_page_size() as isize;
+        self.move_focus_by(amount);
+    }
+
+    pub fn page_up(&mut self) {
+        let amount = self.get_page_size() as isize;
+        self.move_focus_by(-amount);
+    }
+
+    pub fn half_page_down(&mut self) {
+        let amount = cmp::max(self.get_page_size() / 2, 1) as isize;
+        self.move_focus_by(amount);
+    }
+
+    pub fn half_page_up(&mut self) {
+        let amount = cmp::max(self.get_page_size() / 2, 1) as isize;
+        self.move_focus_by(-amount);
+    }
+
+    fn get_page_size(&self) -> usize {
+        let list = self.get_focused_list(&self.state);
+
+        cmp::max(list.visible, 1)
+    }
+
+    fn move_focus_by(&mut self, amount: isize) {
+        let tasks = self.get_focusable_tasks();
+        let list = self.get_mut_focused_list(&self.state.clone());
+
+        if let Some(i) = get_position(&tasks, list.get_selected_index()) {
+            let i = (i as isize + amount).clamp(0, tasks.len() as isize - 1);
+            list.select(Some(tasks[i as usize]));
+        }
+    }
+
     pub fn next_list(&mut self) {
         self.active_list = (self.active_list + 1) % self.task_lists.len();
     }
@@ -300,10 +855,14 @@ impl App {
     }
 
     pub fn task_up(&mut self) {
+        // Tasks swap with their neighbour in the same lane, so tasks in
+        // other lanes keep their places
+        let tasks = self.get_focusable_tasks();
         let list = self.get_mut_focused_list(&self.state.clone());
 
-        if let Some(i) = list.get_selected_index() {
-            if let Some(index) = i.checked_sub(1) {
+        if let Some(pos) = get_position(&tasks, list.get_selected_index()) {
+            if let Some(pos_above) = pos.checked_sub(1) {
+                let (i, index) = (tasks[pos], tasks[pos_above]);
                 list.swap(i, index);
                 list.select(Some(index));
 
@@ -313,13 +872,12 @@ impl App {
     }
 
     pub fn task_down(&mut self) {
+        let tasks = self.get_focusable_tasks();
         let list = self.get_mut_focused_list(&self.state.clone());
 
-        if let Some(i) = list.get_selected_index() {
-            let mut index = i + 1;
-            if index >= list.len() {
-                index = list.len() - 1;
-            }
+        if let Some(pos) = get_position(&tasks, list.get_selected_index()) {
+            let pos_below = cmp::min(pos + 1, tasks.len() - 1);
+            let (i, index) = (tasks[pos], tasks[pos_below]);
 
             list.swap(i, index);
             list.select(Some(index));
@@ -355,15 +913,16 @@ impl App {
     pub fn jump_to_list_top(&mut self) {
         match self.state {
             AppState::ProjectMenu => {
-                if let Some(_) = self.project_list.get_selected_index() {
+                if self.project_list.get_selected_index().is_some() {
                     self.project_list.select(Some(0));
                 }
             },
             _ => {
+                let tasks = self.get_focusable_tasks();
                 let list = self.get_mut_focused_list(&self.state.clone());
 
-                if let Some(_) = list.get_selected_index() {
-                    list.select(Some(0));
+                if list.get_selected_index().is_some() {
+                    list.select(tasks.first().copied());
                 }
             }
         }
@@ -372,15 +931,16 @@ impl App {
     pub fn jump_to_list_bottom(&mut self) {
         match self.state {
             AppState::ProjectMenu => {
-                if let Some(_) = self.project_list.get_selected_index() {
+                if self.project_list.get_selected_index().is_some() {
                     self.project_list.select(Some(self.project_list.len() - 1));
                 }
             },
             _ => {
+                let tasks = self.get_focusable_tasks();
                 let list = self.get_mut_focused_list(&self.state.clone());
 
-                if let Some(_) = list.get_selected_index() {
-                    list.select(Some(list.len() - 1));
+                if list.get_selected_index().is_some() {
+                    list.select(tasks.last().copied());
                 }
             }
         }
@@ -407,75 +967,84 @@ impl App {
         self.unsaved_changes = true;
     }
 
-    pub fn move_task_to_next_list(&mut self) {
+    pub fn move_task_to_next_list(&mut self, steps: usize) {
         if self.active_list != self.task_lists.len() - 1 {
             let list = &mut self.task_lists[self.active_list];
 
-            match list.get_selected_index() {
-                Some(i) => {
-                    let task = list.remove(i);
-                    if list.len() == 0 {
-                        list.select(None);
-                    } else if i == list.len() {
-                        list.select(Some(i - 1));
-                    }
+            if let Some(i) = list.get_selected_index() {
+                let mut task = list.remove(i);
+                self.reselect_after_removal(self.active_list, i);
 
-                    self.next_list();
-                    let list = &mut self.task_lists[self.active_list];
+                let source = ListRef::Tracker(self.active_list);
+                self.active_list = cmp::min(self.active_list + steps, self.task_lists.len() - 1);
+                self.file_task(&mut task, source, ListRef::Tracker(self.active_list));
+                let list = &mut self.task_lists[self.active_list];
 
-                    list.push(task);
-                    list.select(Some(list.len() - 1));
+                let i = list.add(task);
+                list.select(Some(i));
 
-                    self.unsaved_changes = true;
-                },
-                None => {}
+                self.unsaved_changes = true;
             }
         }
     }
 
-    pub fn move_task_to_prev_list(&mut self) {
+    fn reselect_after_removal(&mut self, list_index: usize, removed: usize) {
+        // The task after the removed one takes its place, or failing that the
+        // one before it, sticking to the tasks shown in the active lane
+        let tasks = self.get_shown_tasks(list_index);
+        let next = tasks
+            .iter()
+            .find(|i| **i >= removed)
+            .or(tasks.last())
+            .copied();
+
+        self.task_lists[list_index].select(next);
+    }
+
+    pub fn move_task_to_prev_list(&mut self, steps: usize) {
         if self.active_list != 0 {
             let list = &mut self.task_lists[self.active_list];
 
-            match list.get_selected_index() {
-                Some(i) => {
-                    let task = list.remove(i);
-                    if list.len() == 0 {
-                        list.select(None);
-                    } else if i == list.len() {
-                        list.select(Some(i - 1));
-                    }
+            if let Some(i) = list.get_selected_index() {
+                let mut task = list.remove(i);
+                self.reselect_after_removal(self.active_list, i);
 
-                    self.prev_list();
-                    let list = &mut self.task_lists[self.active_list];
+                let source = ListRef::Tracker(self.active_list);
+                self.active_list = self.active_list.saturating_sub(steps);
+                self.file_task(&mut task, source, ListRef::Tracker(self.active_list));
+                let list = &mut self.task_lists[self.active_list];
 
-                    list.push(task);
-                    list.select(Some(list.len() - 1));
+                let i = list.add(task);
+                list.select(Some(i));
 
-                    self.unsaved_changes = true;
-                },
-                None => {}
+                self.unsaved_changes = true;
             }
         }
     }
 
     pub fn move_task_to_list(&mut self, index: usize) {
-        if index >= self.task_lists.len() {
+        // Moving a task to the list it's already in leaves it where it is
+        let source = self.get_focused_list_ref(&self.state);
+        if index >= self.task_lists.len() || source == ListRef::Tracker(index) {
             return;
         }
 
         let list = self.get_mut_focused_list(&self.state.clone());
 
         if let Some(i) = list.get_selected_index() {
-            let task = list.remove(i);
-            if list.len() == 0 {
+            let mut task = list.remove(i);
+            if list.is_empty() {
                 list.select(None);
             } else if i == list.len() {
                 list.select(Some(i - 1));
             }
+            if let ListRef::Tracker(list_index) = source {
+                self.reselect_after_removal(list_index, i);
+            }
 
+            self.file_task(&mut task, source, ListRef::Tracker(index));
             let dest = &mut self.task_lists[index];
-            dest.push(task);
+            dest.add(task);
             if dest.len() == 1 {
                 dest.select(Some(0));
             }
@@ -488,15 +1057,12 @@ impl App {
         let list = &mut self.task_lists[self.active_list];
 
         if let Some(i) = list.get_selected_index() {
-            let task = list.remove(i);
-            if list.len() == 0 {
-                list.select(None);
-            } else if i == list.len() {
-                list.select(Some(i - 1));
-            }
+            let mut task = list.remove(i);
+            self.reselect_after_removal(self.active_list, i);
 
+            self.file_task(&mut task, ListRef::Tracker(self.active_list), ListRef::Backlog);
             let dest = &mut self.backlog;
-            dest.push(task);
+            dest.add(task);
             if dest.len() == 1 {
                 dest.select(Some(0));
             }
@@ -508,15 +1074,12 @@ impl App {
     pub fn move_task_to_archive(&mut self) {
         let list = &mut self.task_lists[self.active_list];
         if let Some(i) = list.get_selected_index() {
-            let task = list.remove(i);
-            if list.len() == 0 {
-                list.select(None);
-            } else if i == list.len() {
-                list.select(Some(i - 1));
-            }
+            let mut task = list.remove(i);
+            self.reselect_after_removal(self.active_list, i);
 
+            self.file_task(&mut task, ListRef::Tracker(self.active_list), ListRef::Archive);
             let dest = &mut self.archive;
-            dest.push(task);
+            dest.add(task);
             if dest.len() == 1 {
                 dest.select(Some(0));
             }
@@ -525,74 +1088,1148 @@ impl App {
         }
     }
 
-    pub fn change_state(&mut self, state: AppState) {
-        self.state = state;
-    }
-
-    pub fn get_selected_task(&self) -> Option<&Task> {
-        let list = self.get_focused_list(&self.state);
+    pub fn restore_task(&mut self) {
+        let i = match self.archive.get_selected_index() {
+            Some(i) => i,
+            None => return
+        };
+        let dest = match self.get_move_destination(TaskMove::Restore, (ListRef::Archive, i)) {
+            Some(dest) => dest,
+            None => return
+        };
 
-        match list.state.selected() {
-            Some(i) => Some(&list.tasks[i]),
-            None => None
+        let mut task = self.archive.remove(i);
+        self.file_task(&mut task, ListRef::Archive, dest);
+        if self.archive.is_empty() {
+            self.archive.select(None);
+        } else if i == self.archive.len() {
+            self.archive.select(Some(i - 1));
         }
-    }
+        self.sync_archive_focus();
 
-    pub fn focused_list_is_empty(&self) -> bool {
-        let list = self.get_focused_list(&self.state);
+        let list = self.get_mut_list(dest);
+        list.add(task);
+        if list.len() == 1 {
+            list.select(Some(0));
+        }
+        self.message = Some(format!("Restored to '{}'", list.name));
 
-        list.is_empty()
+        self.unsaved_changes = true;
     }
 
-    pub fn scroll_details(&mut self, amount: i16) {
-        let mut new_scroll = self.detail_scroll as i16 + amount;
-        if new_scroll < 0 {
-            new_scroll = 0;
-        }
-        self.detail_scroll = new_scroll as u16;
+    fn file_task(&self, task: &mut Task, source: ListRef, dest: ListRef) {
+        self.file_task_from(task, &self.get_list_origin(source), dest);
     }
 
-    pub fn reset_scroll(&mut self) {
-        self.detail_scroll = 0;
+    fn file_task_from(&self, task: &mut Task, source: &ListOrigin, dest: ListRef) {
+        file_task_between(task, source, &self.get_list_origin(dest));
     }
 
-    pub fn cycle_list_color(&mut self, amount: i8) {
-        let list = self.get_mut_focused_list(&self.state.clone());
-
-        let mut new_color = list.color_index as i8 + amount;
-        if new_color < 1 {
-            new_color = 7;
-        } else if new_color > 7 {
-            new_color = 1;
+    fn get_restore_destination(&self, task: &Task) -> ListRef {
+        // Tasks go back to the list they were archived from if it's still
+        // around, and to the last list otherwise
+        let name = task.archived_from.as_deref().unwrap_or_default();
+        if name == self.backlog.name {
+            return ListRef::Backlog;
         }
-        list.color_index = new_color as u8;
 
-        self.unsaved_changes = true;
+        match self.task_lists.iter().position(|list| list.name == name) {
+            Some(i) => ListRef::Tracker(i),
+            None => ListRef::Tracker(self.task_lists.len() - 1)
+        }
     }
 
-    pub fn save_changes(&mut self) -> Result<(), std::io::Error> {
-        if self.project_title != "" {
-            save_tracker_file(&self.task_lists)?;
-            save_backlog_file(&self.backlog)?;
-            save_archive_file(&self.archive)?;
-            self.unsaved_changes = false;
+    pub fn auto_archive(&mut self) {
+        // Lists with an archive age send the tasks that have been in them
+        // that long to the archive
+        self.last_auto_archive = Instant::now();
+        if self.project_title.is_empty() {
+            return;
         }
 
-        Ok(())
-    }
+        let mut archived = Vec::new();
+        for list in self.task_lists.iter_mut() {
+            let tasks = list.take_finished_tasks();
+            if tasks.is_empty() {
+                continue;
+            }
 
-    pub fn populate_task_detail_inputs(&mut self) {
-        if let Some(task) = self.get_selected_task() {
-            let description: String;
-            match &task.description {
-                Some(d) => description = d.to_string(),
-                None => description = String::new()
+            archived.push(format!("{} from '{}'", count_tasks(tasks.len()), list.name));
+            for mut task in tasks {
+                task.marked = false;
+                task.archive_from(&list.name);
+                self.archive.add(task);
+            }
+        }
+
+        if archived.is_empty() {
+            return;
+        }
+        if self.archive.get_selected_index().is_none() {
+            self.archive.select(Some(0));
+        }
+
+        self.message = Some(format!("Archived {}", archived.join(", ")));
+        self.unsaved_changes = true;
+    }
+
+    pub fn check_auto_archive(&mut self) {
+        // Waits while a prompt or editor is open, since those work on the
+        // focused task and shouldn't have it swapped out from under them
+        let idle = matches!(
+            self.state,
+            AppState::Tracker | AppState::BacklogPopup(_) | AppState::ArchivePopup(_)
+        );
+
+        if idle && self.last_auto_archive.elapsed() >= AUTO_ARCHIVE_INTERVAL {
+            self.auto_archive();
+        }
+    }
+
+    pub fn focus_list(&mut self, index: usize) {
+        if index < self.task_lists.len() {
+            self.active_list = index;
+        }
+    }
+
+    pub fn focus_task(&mut self, index: usize) {
+        let list = self.get_mut_focused_list(&self.state.clone());
+
+        if index < list.len() {
+            list.select(Some(index));
+        }
+    }
+
+    pub fn drop_task(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let (list_index, task_index) = from;
+        let (dest_index, dest_task_index) = to;
+
+        if from == to
+            || list_index >= self.task_lists.len()
+            || dest_index >= self.task_lists.len()
+            || task_index >= self.task_lists[list_index].len() {
+            return;
+        }
+
+        let list = &mut self.task_lists[list_index];
+        let mut task = list.remove(task_index);
+        if list.is_empty() {
+            list.select(None);
+        } else if task_index >= list.len() {
+            list.select(Some(list.len() - 1));
+        }
+        if list_index != dest_index {
+            self.file_task(&mut task, ListRef::Tracker(list_index), ListRef::Tracker(dest_index));
+        }
+
+        let dest = &mut self.task_lists[dest_index];
+        let i = dest.add_at(cmp::min(dest_task_index, dest.len()), task);
+        dest.select(Some(i));
+        self.active_list = dest_index;
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn request_task_move(&mut self, task_move: TaskMove) {
+        let dest_index = match self.get_limited_destination(task_move) {
+            Some(i) => i,
+            None => {
+                self.perform_task_move(task_move);
+                return;
+            }
+        };
+
+        match self.config.wip_limit_policy {
+            WipLimitPolicy::Confirm => {
+                self.pending_move = Some(task_move);
+                self.change_state(AppState::ConfirmMove(Box::new(self.state.clone())));
+            },
+            WipLimitPolicy::Refuse => {
+                self.message = Some(format!(
+                    "'{}' is at its WIP limit",
+                    self.task_lists[dest_index].name
+                ));
+            }
+        }
+    }
+
+    pub fn confirm_task_move(&mut self) {
+        if let Some(task_move) = self.pending_move.take() {
+            self.perform_task_move(task_move);
+        }
+    }
+
+    pub fn cancel_task_move(&mut self) {
+        self.pending_move = None;
+    }
+
+    pub fn get_pending_move_list_name(&self) -> String {
+        match self.pending_move.and_then(|m| self.get_limited_destination(m)) {
+            Some(i) => self.task_lists[i].name.clone(),
+            None => String::new()
+        }
+    }
+
+    fn get_move_destination(
+        &self,
+        task_move: TaskMove,
+        (source, task_index): (ListRef, usize)
+    ) -> Option<ListRef> {
+        let num_lists = self.task_lists.len();
+        let dest = match (task_move, source) {
+            (TaskMove::NextList(n), ListRef::Tracker(i)) if i + 1 < num_lists => {
+                ListRef::Tracker(cmp::min(i + n, num_lists - 1))
+            },
+            (TaskMove::PrevList(n), ListRef::Tracker(i)) if i > 0 => {
+                ListRef::Tracker(i.saturating_sub(n))
+            },
+            (TaskMove::ToList(i), _) if i < num_lists => ListRef::Tracker(i),
+            (TaskMove::Drop(_, (i, _)), _) if i < num_lists => ListRef::Tracker(i),
+            (TaskMove::Backlog, _) => ListRef::Backlog,
+            (TaskMove::Archive, _) => ListRef::Archive,
+            (TaskMove::Restore, ListRef::Archive) => {
+                self.get_restore_destination(&self.archive.tasks[task_index])
+            },
+            _ => return None
+        };
+
+        if dest == source {
+            None
+        } else {
+            Some(dest)
+        }
+    }
+
+    fn get_moving_tasks(&self, task_move: TaskMove) -> Vec<(ListRef, usize)> {
+        // Every task the move would take somewhere else, with its source list
+        match task_move {
+            TaskMove::Drop((list_index, task_index), _) => {
+                vec![(ListRef::Tracker(list_index), task_index)]
+            },
+            TaskMove::Paste(..) => Vec::new(),
+            _ => self.get_target_tasks()
+        }
+    }
+
+    fn get_limited_destination(&self, task_move: TaskMove) -> Option<usize> {
+        // Returns a destination list the move would take over its limit
+        let mut incoming = vec![0; self.task_lists.len()];
+        if let TaskMove::Paste(_, count) = task_move {
+            if let ListRef::Tracker(i) = self.get_focused_list_ref(&self.state) {
+                incoming[i] += self.register.len() * count;
+            }
+        }
+        for task in self.get_moving_tasks(task_move) {
+            if let Some(ListRef::Tracker(i)) = self.get_move_destination(task_move, task) {
+                incoming[i] += 1;
+            }
+        }
+
+        (0..self.task_lists.len())
+            .find(|i| incoming[*i] > 0 && self.task_lists[*i].would_exceed_limit(incoming[*i]))
+    }
+
+    fn perform_task_move(&mut self, task_move: TaskMove) {
+        match task_move {
+            TaskMove::Drop(from, to) => self.drop_task(from, to),
+            TaskMove::Paste(above, count) => (0..count).for_each(|_| self.paste_tasks(above)),
+            _ if self.has_marked_tasks() => self.move_marked_tasks(task_move),
+            TaskMove::NextList(n) => self.move_task_to_next_list(n),
+            TaskMove::PrevList(n) => self.move_task_to_prev_list(n),
+            TaskMove::ToList(i) => self.move_task_to_list(i),
+            TaskMove::Backlog => self.move_task_to_backlog(),
+            TaskMove::Archive => self.move_task_to_archive(),
+            TaskMove::Restore => self.restore_task(),
+        }
+    }
+
+    fn get_focused_list_ref(&self, state: &AppState) -> ListRef {
+        match state {
+            AppState::Tracker => ListRef::Tracker(self.active_list),
+            AppState::BacklogPopup(_) => ListRef::Backlog,
+            AppState::ArchivePopup(_) => ListRef::Archive,
+            AppState::ArchiveSearch(prev) => self.get_focused_list_ref(prev),
+            AppState::TaskView(prev) => self.get_focused_list_ref(prev),
+            AppState::EditTask(prev) => self.get_focused_list_ref(prev),
+            AppState::CreateTask(prev) => self.get_focused_list_ref(prev),
+            AppState::DeleteTask(prev) => self.get_focused_list_ref(prev),
+            AppState::SetCategory(prev) => self.get_focused_list_ref(prev),
+            AppState::SendToProject(prev) => self.get_focused_list_ref(prev),
+            AppState::SendToProjectList(prev) => self.get_focused_list_ref(prev),
+            AppState::MoveToList(prev) => self.get_focused_list_ref(prev),
+            AppState::CommandLine(prev) => self.get_focused_list_ref(prev),
+            AppState::EditList(prev) => self.get_focused_list_ref(prev),
+            AppState::CreateList(prev) => self.get_focused_list_ref(prev),
+            AppState::DeleteList(prev) => self.get_focused_list_ref(prev),
+            AppState::ConfirmMove(prev) => self.get_focused_list_ref(prev),
+            AppState::Statistics(prev) => self.get_focused_list_ref(prev),
+            AppState::FlowCharts(prev) => self.get_focused_list_ref(prev),
+            AppState::SetBurndownTarget(prev) => self.get_focused_list_ref(prev),
+            AppState::Help(prev) => self.get_focused_list_ref(prev),
+            AppState::HelpSearch(prev) => self.get_focused_list_ref(prev),
+            _ => unreachable!()
+        }
+    }
+
+    fn get_list(&self, list_ref: ListRef) -> &TaskList {
+        match list_ref {
+            ListRef::Tracker(i) => &self.task_lists[i],
+            ListRef::Backlog => &self.backlog,
+            ListRef::Archive => &self.archive,
+        }
+    }
+
+    fn get_mut_list(&mut self, list_ref: ListRef) -> &mut TaskList {
+        match list_ref {
+            ListRef::Tracker(i) => &mut self.task_lists[i],
+            ListRef::Backlog => &mut self.backlog,
+            ListRef::Archive => &mut self.archive,
+        }
+    }
+
+    fn get_list_refs(&self) -> Vec<ListRef> {
+        let mut list_refs: Vec<ListRef> = (0..self.task_lists.len())
+            .map(ListRef::Tracker)
+            .collect();
+        list_refs.push(ListRef::Backlog);
+        list_refs.push(ListRef::Archive);
+
+        list_refs
+    }
+
+    pub fn toggle_mark(&mut self) {
+        let list_ref = self.get_focused_list_ref(&self.state);
+        let list = self.get_mut_list(list_ref);
+
+        if let Some(i) = list.get_selected_index() {
+            list.tasks[i].marked = !list.tasks[i].marked;
+            self.mark_anchor = Some((list_ref, i));
+        }
+    }
+
+    pub fn mark_range(&mut self) {
+        // Marks everything between the last toggled task and the focused one
+        let list_ref = self.get_focused_list_ref(&self.state);
+        let tasks = self.get_focusable_tasks();
+        let selected = get_position(&tasks, self.get_list(list_ref).get_selected_index());
+
+        let selected = match selected {
+            Some(i) => i,
+            None => return
+        };
+        let anchor = match self.mark_anchor {
+            Some((anchor_ref, i)) if anchor_ref == list_ref => {
+                get_position(&tasks, Some(i)).unwrap_or(selected)
+            },
+            _ => selected
+        };
+
+        let list = self.get_mut_list(list_ref);
+        for i in &tasks[cmp::min(anchor, selected)..=cmp::max(anchor, selected)] {
+            list.tasks[*i].marked = true;
+        }
+        self.mark_anchor = Some((list_ref, tasks[selected]));
+    }
+
+    pub fn clear_marks(&mut self) {
+        for list_ref in self.get_list_refs() {
+            for task in self.get_mut_list(list_ref).tasks.iter_mut() {
+                task.marked = false;
+            }
+        }
+        self.mark_anchor = None;
+    }
+
+    pub fn has_marked_tasks(&self) -> bool {
+        self.num_marked_tasks() > 0
+    }
+
+    pub fn num_marked_tasks(&self) -> usize {
+        self.get_marked_tasks().len()
+    }
+
+    fn get_marked_tasks(&self) -> Vec<(ListRef, usize)> {
+        let mut marked = Vec::new();
+        for list_ref in self.get_list_refs() {
+            for (i, task) in self.get_list(list_ref).tasks.iter().enumerate() {
+                if task.marked {
+                    marked.push((list_ref, i));
+                }
+            }
+        }
+
+        marked
+    }
+
+    fn remove_tasks(&mut self, tasks: &[(ListRef, usize)]) -> Vec<Task> {
+        // Going backwards keeps the indices of the tasks still to be removed
+        // valid, and the result comes back in the original order
+        let mut removed = Vec::new();
+        for (list_ref, i) in tasks.iter().rev() {
+            removed.push(self.get_mut_list(*list_ref).remove(*i));
+        }
+        removed.reverse();
+
+        for (list_ref, _) in tasks.iter() {
+            let list = self.get_mut_list(*list_ref);
+            if list.is_empty() {
+                list.select(None);
+            } else if let Some(i) = list.get_selected_index() {
+                list.select(Some(cmp::min(i, list.len() - 1)));
+            }
+        }
+        self.mark_anchor = None;
+
+        removed
+    }
+
+    fn move_marked_tasks(&mut self, task_move: TaskMove) {
+        let mut sources = Vec::new();
+        let mut dests = Vec::new();
+        for (source, i) in self.get_marked_tasks() {
+            if let Some(dest) = self.get_move_destination(task_move, (source, i)) {
+                sources.push((source, i));
+                dests.push(dest);
+            }
+        }
+
+        // Every task is taken out before any are put back, so that a task
+        // moved into a list with marked tasks of its own doesn't move twice
+        let tasks = self.remove_tasks(&sources);
+        for (((source, _), dest), mut task) in sources.into_iter().zip(dests).zip(tasks) {
+            self.file_task(&mut task, source, dest);
+
+            let list = self.get_mut_list(dest);
+            list.add(task);
+            if list.len() == 1 {
+                list.select(Some(0));
+            }
+        }
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn delete_marked_tasks(&mut self) {
+        let marked = self.get_marked_tasks();
+        self.remove_tasks(&marked);
+
+        self.unsaved_changes = true;
+    }
+
+    fn get_target_tasks(&self) -> Vec<(ListRef, usize)> {
+        // Marked tasks if there are any, otherwise just the focused task
+        if self.has_marked_tasks() {
+            return self.get_marked_tasks();
+        }
+
+        let list_ref = self.get_focused_list_ref(&self.state);
+        match self.get_list(list_ref).get_selected_index() {
+            Some(i) => vec![(list_ref, i)],
+            None => Vec::new()
+        }
+    }
+
+    fn get_list_origin(&self, list: ListRef) -> ListOrigin {
+        ListOrigin {
+            project: self.project_title.clone(),
+            list,
+            name: self.get_list(list).name.clone(),
+        }
+    }
+
+    pub fn yank_tasks(&mut self) {
+        let targets = self.get_target_tasks();
+        if targets.is_empty() {
+            return;
+        }
+
+        self.register = targets
+            .iter()
+            .map(|(list_ref, i)| {
+                (self.get_list_origin(*list_ref), self.get_list(*list_ref).tasks[*i].clone())
+            })
+            .collect();
+        self.clear_marks();
+        // Time spent stays with the original, so copies start from nothing
+        for (_, task) in self.register.iter_mut() {
+            task.marked = false;
+            task.time_sessions.clear();
+        }
+
+        self.message = Some(format!("Yanked {}", count_tasks(self.register.len())));
+    }
+
+    pub fn cut_tasks(&mut self) {
+        let targets = self.get_target_tasks();
+        if targets.is_empty() {
+            return;
+        }
+
+        let origins: Vec<ListOrigin> = targets
+            .iter()
+            .map(|(list_ref, _)| self.get_list_origin(*list_ref))
+            .collect();
+        let tasks = self.remove_tasks(&targets);
+        self.register = origins.into_iter().zip(tasks).collect();
+        self.clear_marks();
+        for (_, task) in self.register.iter_mut() {
+            task.marked = false;
+        }
+
+        self.message = Some(format!("Cut {}", count_tasks(self.register.len())));
+        self.unsaved_changes = true;
+    }
+
+    fn paste_tasks(&mut self, above: bool) {
+        if self.register.is_empty() {
+            return;
+        }
+
+        // The register is kept after pasting, so tasks can be pasted again.
+        // Tasks pasted into another list have moved there, like any other move
+        let dest = self.get_focused_list_ref(&self.state);
+        let dest_origin = self.get_list_origin(dest);
+        let mut tasks = Vec::new();
+        for (source, mut task) in self.register.clone() {
+            if source != dest_origin {
+                self.file_task_from(&mut task, &source, dest);
+            }
+            tasks.push(task);
+        }
+
+        let list = self.get_mut_focused_list(&self.state.clone());
+        let index = match list.get_selected_index() {
+            Some(i) if above => i,
+            Some(i) => i + 1,
+            None => list.len()
+        };
+        for (n, task) in tasks.into_iter().enumerate() {
+            // Later tasks keep the selection on the first, wherever it went
+            let i = list.add_at(index + n, task);
+            if n == 0 {
+                list.select(Some(i));
+            }
+        }
+
+        // A cut task is only moved by the first paste, and any after that
+        // are copies
+        for (_, task) in self.register.iter_mut() {
+            task.time_sessions.clear();
+        }
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn open_command_line(&mut self) {
+        self.command_input.clear();
+        self.completion = None;
+        self.change_state(AppState::CommandLine(Box::new(self.state.clone())));
+    }
+
+    pub fn run_command(&mut self) -> Result<(), std::io::Error> {
+        let line = self.command_input.extract();
+        let command = match Command::parse(&line) {
+            Ok(command) => command,
+            Err(message) => {
+                self.message = Some(message);
+                return Ok(());
+            }
+        };
+
+        match command {
+            Command::Write => self.save_changes()?,
+            Command::Quit | Command::WriteQuit => self.set_quit(true),
+            Command::ForceQuit => {
+                self.discard_changes = true;
+                self.set_quit(true);
+            },
+            Command::Archive => self.apply_change(Change::Move(TaskMove::Archive)),
+            Command::Backlog => self.apply_change(Change::Move(TaskMove::Backlog)),
+            Command::Move(name) => {
+                match self.find_list(&name) {
+                    Some(i) => self.apply_change(Change::Move(TaskMove::ToList(i))),
+                    None => self.message = Some(format!("No list named '{}'", name))
+                }
+            },
+            Command::Sort(order) => {
+                self.get_mut_focused_list(&self.state.clone()).sort(order);
+                self.unsaved_changes = true;
+            },
+            Command::AutoSort(order) => {
+                let list = self.get_mut_focused_list(&self.state.clone());
+                list.auto_sort = order;
+                list.resort();
+                self.unsaved_changes = true;
+            },
+            Command::Filter(filter) => self.set_filter(filter),
+            Command::Project(name) => {
+                if let Err(err) = self.switch_project(&name) {
+                    self.message = Some(format!("Couldn't open '{}': {}", name, err));
+                }
+            },
+            Command::Help => self.open_help(),
+        }
+
+        Ok(())
+    }
+
+    fn find_list(&self, name: &str) -> Option<usize> {
+        // An exact name wins, otherwise the name only has to start the same
+        let name = name.to_lowercase();
+        let names: Vec<String> = self.task_lists
+            .iter()
+            .map(|list| list.name.to_lowercase())
+            .collect();
+
+        names.iter()
+            .position(|n| *n == name)
+            .or_else(|| names.iter().position(|n| n.starts_with(&name)))
+    }
+
+    fn switch_project(&mut self, name: &str) -> Result<(), std::io::Error> {
+        let index = match self.project_list.projects.iter().position(|p| p == name) {
+            Some(i) => i,
+            None => {
+                self.message = Some(format!("No project named '{}'", name));
+                return Ok(());
+            }
+        };
+
+        if name != self.project_title {
+            // Make sure the other project can be read before leaving this one
+            let dir = get_project_dir(name)?;
+            read_tracker_file(&dir)?;
+            read_backlog_file(&dir)?;
+            read_archive_file(&dir)?;
+
+            self.save_changes()?;
+            self.move_up_one_directory()?;
+            self.project_list.select(Some(index));
+            self.select_project()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn complete_command(&mut self, forward: bool) {
+        // Repeated completions cycle through the candidates found the first
+        // time, so typing or deleting anything starts over
+        if self.completion.is_none() {
+            let text = self.command_input.text.clone();
+            let (stem, word) = match text.split_once(' ') {
+                Some((name, arg)) => (format!("{} ", name), arg.to_string()),
+                None => (String::new(), text.clone())
+            };
+
+            let candidates: Vec<String> = self.get_command_candidates(&stem)
+                .into_iter()
+                .filter(|c| c.to_lowercase().starts_with(&word.to_lowercase()))
+                .collect();
+            if candidates.is_empty() {
+                return;
+            }
+
+            // Start before the first candidate, so the first step lands on it
+            let index = if forward { candidates.len() - 1 } else { 0 };
+            self.completion = Some(Completion { stem, candidates, index });
+        }
+
+        if let Some(completion) = &mut self.completion {
+            let len = completion.candidates.len();
+            completion.index = if forward {
+                (completion.index + 1) % len
+            } else {
+                (completion.index + len - 1) % len
+            };
+
+            let text = format!("{}{}", completion.stem, completion.candidates[completion.index]);
+            self.command_input = Input::from(text).name("Command".to_string());
+        }
+    }
+
+    fn get_command_candidates(&self, stem: &str) -> Vec<String> {
+        match stem.trim() {
+            "" => COMMAND_NAMES.iter().map(|name| name.to_string()).collect(),
+            "move" => self.task_lists.iter().map(|list| list.name.clone()).collect(),
+            "project" => self.project_list.projects.clone(),
+            "sort" => SortKey::NAMES.iter().map(|name| name.to_string()).collect(),
+            "autosort" => SortKey::NAMES
+                .iter()
+                .chain(["off"].iter())
+                .map(|name| name.to_string())
+                .collect(),
+            "filter" => {
+                let mut categories: Vec<String> = self.task_lists
+                    .iter()
+                    .flat_map(|list| list.tasks.iter())
+                    .filter_map(|task| task.category.as_ref())
+                    .map(|category| format!("cat={}", category))
+                    .collect();
+                categories.sort();
+                categories.dedup();
+                categories
+            },
+            _ => Vec::new()
+        }
+    }
+
+    pub fn open_list_picker(&mut self) {
+        if self.get_target_tasks().is_empty() {
+            return;
+        }
+
+        let lists: Vec<String> = self.task_lists
+            .iter()
+            .enumerate()
+            .map(|(i, list)| format!("{}. {}", i + 1, list.name))
+            .collect();
+
+        self.picker = Picker::from("Move To List".to_string(), lists);
+        self.change_state(AppState::MoveToList(Box::new(self.state.clone())));
+    }
+
+    pub fn open_project_picker(&mut self, copy: bool) {
+        if self.get_target_tasks().is_empty() {
+            return;
+        }
+
+        let projects: Vec<String> = self.project_list.projects
+            .iter()
+            .filter(|project| **project != self.project_title)
+            .cloned()
+            .collect();
+
+        if projects.is_empty() {
+            self.message = Some("There are no other projects".to_string());
+            return;
+        }
+
+        let title = if copy {
+            "Copy To Project"
+        } else {
+            "Send To Project"
+        };
+        self.picker = Picker::from(title.to_string(), projects);
+        self.send_copy = copy;
+        self.change_state(AppState::SendToProject(Box::new(self.state.clone())));
+    }
+
+    pub fn pick_project(&mut self) -> Result<(), std::io::Error> {
+        let project = match self.picker.get_selected_item() {
+            Some(i) => self.picker.items[i].clone(),
+            None => return Ok(())
+        };
+
+        // The other project's backlog comes first, then its tracker lists
+        let dir = get_project_dir(&project)?;
+        let mut lists = vec![peek_backlog_file(&dir)?.name];
+        lists.extend(
+            peek_tracker_file(&dir)?
+            .into_iter()
+            .map(|list| list.name)
+        );
+
+        self.picker = Picker::from(format!("Lists In '{}'", project), lists);
+        self.send_project = project;
+        if let AppState::SendToProject(prev) = self.state.clone() {
+            self.change_state(AppState::SendToProjectList(prev));
+        }
+
+        Ok(())
+    }
+
+    pub fn send_tasks_to_project(&mut self) -> Result<(), std::io::Error> {
+        let dest_index = match self.picker.get_selected_item() {
+            Some(i) => i,
+            None => return Ok(())
+        };
+
+        // Written straight to the other project's files, which aren't open.
+        // They're read again here since the lists may have changed since
+        // they were picked
+        let dir = get_project_dir(&self.send_project)?;
+        let mut backlog = peek_backlog_file(&dir)?;
+        let mut task_lists = peek_tracker_file(&dir)?;
+        let (list, dest) = match dest_index {
+            0 => (&mut backlog, ListRef::Backlog),
+            i => match task_lists.get_mut(i - 1) {
+                Some(list) => (list, ListRef::Tracker(i - 1)),
+                None => return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("'{}' no longer has that list", self.send_project)
+                ))
+            }
+        };
+        let dest_origin = ListOrigin {
+            project: self.send_project.clone(),
+            list: dest,
+            name: list.name.clone(),
+        };
+
+        let targets = self.get_target_tasks();
+        for (list_ref, i) in targets.iter() {
+            let mut task = Task {
+                id: None,
+                marked: false,
+                archived_from: None,
+                archived_at: None,
+                ..self.get_list(*list_ref).tasks[*i].clone()
+            };
+            // Copies leave the time spent with the original, and sent tasks
+            // stop timing since the other project isn't open
+            if self.send_copy {
+                task.time_sessions.clear();
+            } else {
+                task.stop_timer();
+            }
+            file_task_between(&mut task, &self.get_list_origin(*list_ref), &dest_origin);
+            list.add(task);
+        }
+        let num_tasks = targets.len();
+
+        match dest {
+            ListRef::Backlog => save_backlog_file(&dir, &backlog)?,
+            _ => save_tracker_file(&dir, &task_lists)?,
+        }
+
+        if !self.send_copy {
+            self.remove_tasks(&targets);
+            self.unsaved_changes = true;
+        }
+        self.clear_marks();
+
+        self.message = Some(format!(
+            "{} {} to '{}'",
+            if self.send_copy { "Copied" } else { "Sent" },
+            count_tasks(num_tasks),
+            self.send_project
+        ));
+
+        Ok(())
+    }
+
+    pub fn populate_category_input(&mut self) {
+        let category = match self.get_selected_task() {
+            Some(task) if !self.has_marked_tasks() => task.category.clone(),
+            _ => None
+        };
+
+        self.category_input = Input::from(category.unwrap_or_default())
+            .name("Category".to_string());
+    }
+
+    pub fn save_category(&mut self) {
+        let category = self.category_input.extract();
+        self.apply_change(Change::Category(category));
+    }
+
+    fn set_category(&mut self, category: &str) {
+        let category = if category.is_empty() {
+            None
+        } else {
+            Some(category.to_string())
+        };
+
+        if self.has_marked_tasks() {
+            for (list_ref, i) in self.get_marked_tasks() {
+                self.get_mut_list(list_ref).tasks[i].edit(|task| task.category = category.clone());
+            }
+        } else {
+            let list = self.get_mut_focused_list(&self.state.clone());
+            match list.get_selected_index() {
+                Some(i) => list.tasks[i].edit(|task| task.category = category),
+                None => return
+            }
+        }
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn populate_burndown_target_input(&mut self) {
+        let target = self.burndown_target
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+
+        self.burndown_target_input = Input::from(target)
+            .name("Target Date (YYYY-MM-DD)".to_string());
+    }
+
+    pub fn save_burndown_target(&mut self) {
+        let text = self.burndown_target_input.extract();
+        self.burndown_target = match NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) if text.trim().is_empty() => None,
+            Err(_) => {
+                self.message = Some(format!("'{}' isn't a date like 2024-12-31", text));
+                self.burndown_target
+            }
+        };
+    }
+
+    pub fn toggle_timer(&mut self) {
+        let state = self.state.clone();
+        if self.get_focused_list_ref(&state) == ListRef::Archive {
+            self.message = Some("Archived tasks can't be timed".to_string());
+            return;
+        }
+
+        let i = match self.get_focused_list(&state).get_selected_index() {
+            Some(i) => i,
+            None => return
+        };
+
+        let task = &self.get_focused_list(&state).tasks[i];
+        if task.is_timing() {
+            let task = &mut self.get_mut_focused_list(&state).tasks[i];
+            task.stop_timer();
+            self.message = Some(format!(
+                "Stopped timer on '{}', {} in total",
+                task.summary,
+                format_time_spent(task.time_spent())
+            ));
+        } else {
+            // Only one timer runs at a time, so starting one stops the other
+            self.stop_timers();
+            let task = &mut self.get_mut_focused_list(&state).tasks[i];
+            task.start_timer();
+            self.message = Some(format!("Started timer on '{}'", task.summary));
+        }
+
+        self.unsaved_changes = true;
+    }
+
+    fn stop_timers(&mut self) {
+        for list in self.task_lists.iter_mut().chain([&mut self.backlog, &mut self.archive]) {
+            for task in list.tasks.iter_mut() {
+                task.stop_timer();
+            }
+        }
+    }
+
+    pub fn get_timed_task(&self) -> Option<&Task> {
+        self.task_lists
+            .iter()
+            .chain([&self.backlog])
+            .flat_map(|list| &list.tasks)
+            .find(|task| task.is_timing())
+    }
+
+    pub fn push_count_digit(&mut self, digit: usize) {
+        // A leading zero isn't a count, and counts stop growing at 999
+        match self.count {
+            None if digit == 0 => {},
+            None => self.count = Some(digit),
+            Some(count) => self.count = Some(cmp::min(count * 10 + digit, 999)),
+        }
+    }
+
+    pub fn take_count(&mut self) -> usize {
+        self.count.take().unwrap_or(1)
+    }
+
+    pub fn apply_change(&mut self, change: Change) {
+        match &change {
+            Change::TaskDown(n) => (0..*n).for_each(|_| self.task_down()),
+            Change::TaskUp(n) => (0..*n).for_each(|_| self.task_up()),
+            Change::TaskToTop => self.task_to_top(),
+            Change::TaskToBottom => self.task_to_bottom(),
+            Change::Move(task_move) => self.request_task_move(*task_move),
+            Change::Paste(above, n) => {
+                if !self.register.is_empty() {
+                    self.request_task_move(TaskMove::Paste(*above, *n));
+                }
+            },
+            Change::Category(category) => self.set_category(category),
+            Change::Delete => {
+                if self.has_marked_tasks() || !self.focused_list_is_empty() {
+                    self.change_state(AppState::DeleteTask(Box::new(self.state.clone())));
+                }
+            },
+        }
+
+        self.last_change = Some(change);
+    }
+
+    pub fn repeat_change(&mut self, count: usize) {
+        if let Some(change) = self.last_change.clone() {
+            for _ in 0..count {
+                // Stop early if the change opened a prompt
+                if !matches!(
+                    self.state,
+                    AppState::Tracker | AppState::BacklogPopup(_) | AppState::ArchivePopup(_)
+                ) {
+                    break;
+                }
+                self.apply_change(change.clone());
+            }
+        }
+    }
+
+    pub fn register_click(&mut self, column: u16, row: u16) -> bool {
+        // Returns true if this click completes a double-click
+        let now = Instant::now();
+        let double_click = match self.last_click {
+            Some((time, c, r)) => {
+                c == column && r == row && now.duration_since(time).as_millis() < 500
+            },
+            None => false
+        };
+
+        if double_click {
+            self.last_click = None;
+        } else {
+            self.last_click = Some((now, column, row));
+        }
+
+        double_click
+    }
+
+    pub fn change_state(&mut self, state: AppState) {
+        self.state = state;
+    }
+
+    pub fn get_selected_task(&self) -> Option<&Task> {
+        let list = self.get_focused_list(&self.state);
+
+        match list.state.selected() {
+            Some(i) => Some(&list.tasks[i]),
+            None => None
+        }
+    }
+
+    pub fn focused_list_is_empty(&self) -> bool {
+        // With swimlanes on, the focused list is only the active lane's cell
+        self.get_focusable_tasks().is_empty()
+    }
+
+    pub fn scroll_details(&mut self, amount: i16) {
+        let mut new_scroll = self.detail_scroll as i16 + amount;
+        if new_scroll < 0 {
+            new_scroll = 0;
+        }
+        self.detail_scroll = new_scroll as u16;
+    }
+
+    pub fn reset_scroll(&mut self) {
+        self.detail_scroll = 0;
+    }
+
+    pub fn cycle_list_color(&mut self, amount: i8) {
+        let list = self.get_mut_focused_list(&self.state.clone());
+
+        let mut new_color = list.color_index as i8 + amount;
+        if new_color < 1 {
+            new_color = 7;
+        } else if new_color > 7 {
+            new_color = 1;
+        }
+        list.color_index = new_color as u8;
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn cycle_card_mode(&mut self) {
+        let list = self.get_mut_focused_list(&self.state.clone());
+        list.card_mode = list.card_mode.next();
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn cycle_all_card_modes(&mut self) {
+        let card_mode = self.get_focused_list(&self.state).card_mode.next();
+
+        for list in self.task_lists.iter_mut() {
+            list.card_mode = card_mode;
+        }
+        self.backlog.card_mode = card_mode;
+        self.archive.card_mode = card_mode;
+
+        self.unsaved_changes = true;
+    }
+
+    pub fn save_changes(&mut self) -> Result<(), std::io::Error> {
+        if !self.project_title.is_empty() {
+            let dir = env::current_dir()?;
+            self.assign_task_ids();
+            save_tracker_file(&dir, &self.task_lists)?;
+            save_backlog_file(&dir, &self.backlog)?;
+            // Tasks from loaded years go back to their own files, keeping
+            // them out of the main archive file
+            let mut archive = self.archive.clone();
+            archive.tasks.retain(|task| task.archive_year.is_none());
+            for year in &self.loaded_archive_years {
+                let tasks: Vec<Task> = self.archive.tasks
+                    .iter()
+                    .filter(|task| task.archive_year == Some(*year))
+                    .cloned()
+                    .collect();
+                let path = get_archive_year_path(&dir, *year);
+                if !tasks.is_empty() {
+                    save_archive_year_file(&dir, *year, &tasks)?;
+                } else if path.exists() {
+                    fs::remove_file(path)?;
+                }
+            }
+            save_archive_file(&dir, &archive)?;
+            // The first save of each day keeps a snapshot for reports
+            let today = Local::now().date_naive();
+            if !get_snapshot_path(&dir, today).exists() {
+                let snapshot = Snapshot::new(&self.task_lists, &self.backlog, &archive);
+                save_snapshot_file(&dir, today, &snapshot)?;
+            }
+            self.unsaved_changes = false;
+        }
+
+        Ok(())
+    }
+
+    fn assign_task_ids(&mut self) {
+        // Copies keep the id of the task they came from, so only the first
+        // of each id stays and the rest get new ones
+        let mut seen = HashSet::new();
+        for list in self.task_lists.iter_mut().chain([&mut self.backlog, &mut self.archive]) {
+            for task in list.tasks.iter_mut() {
+                if task.id.is_some_and(|id| !seen.insert(id)) {
+                    task.id = None;
+                }
             }
-            let category: String;
-            match &task.category {
-                Some(c) => category = c.to_string(),
-                None => category = String::new()
+        }
+
+        // New ids come from the creation time, moved on past any taken ones
+        let now = Local::now().timestamp_millis() as u64;
+        for list in self.task_lists.iter_mut().chain([&mut self.backlog, &mut self.archive]) {
+            for task in list.tasks.iter_mut().filter(|task| task.id.is_none()) {
+                let mut id = task.created.map_or(now, |created| created.timestamp_millis() as u64);
+                while !seen.insert(id) {
+                    id += 1;
+                }
+                task.id = Some(id);
             }
+        }
+    }
+
+    pub fn populate_task_detail_inputs(&mut self) {
+        if let Some(task) = self.get_selected_task() {
+            let description = match &task.description {
+                Some(d) => d.to_string(),
+                None => String::new()
+            };
+            let category = match &task.category {
+                Some(c) => c.to_string(),
+                None => String::new()
+            };
+            let due = match &task.due {
+                Some(d) => d.format("%Y-%m-%d").to_string(),
+                None => String::new()
+            };
+            let priority = match &task.priority {
+                Some(p) => p.to_string(),
+                None => String::new()
+            };
+            let estimate = match &task.estimate {
+                Some(e) => e.to_string(),
+                None => String::new()
+            };
 
             self.task_detail_inputs[0] = Input::from(task.summary.clone())
                 .name("Summary".to_string());
@@ -600,17 +2237,36 @@ impl App {
                 .name("Description".to_string());
             self.task_detail_inputs[2] = Input::from(category)
                 .name("Category".to_string());
+            self.task_detail_inputs[3] = Input::from(due)
+                .name("Due (YYYY-MM-DD)".to_string());
+            self.task_detail_inputs[4] = Input::from(priority)
+                .name("Priority".to_string());
+            self.task_detail_inputs[5] = Input::from(estimate)
+                .name(self.estimate_input_name());
         }
     }
 
     pub fn get_focused_input(&mut self) -> &mut Input {
-        match self.state {
+        self.get_state_input(&self.state.clone())
+    }
+
+    fn get_state_input(&mut self, state: &AppState) -> &mut Input {
+        match state {
             AppState::EditProject(_) => &mut self.project_detail_input,
             AppState::CreateProject(_) => &mut self.project_detail_input,
             AppState::EditTask(_) => &mut self.task_detail_inputs[self.active_detail_input],
             AppState::CreateTask(_) => &mut self.task_detail_inputs[self.active_detail_input],
-            AppState::EditList(_) => &mut self.list_detail_input,
-            AppState::CreateList(_) => &mut self.list_detail_input,
+            AppState::SetCategory(_) => &mut self.category_input,
+            AppState::SendToProject(_) => &mut self.picker.input,
+            AppState::SendToProjectList(_) => &mut self.picker.input,
+            AppState::MoveToList(_) => &mut self.picker.input,
+            AppState::CommandLine(_) => &mut self.command_input,
+            AppState::SetBurndownTarget(_) => &mut self.burndown_target_input,
+            AppState::ArchiveSearch(_) => &mut self.archive_search_input,
+            AppState::EditList(_) => &mut self.list_detail_inputs[self.active_detail_input],
+            AppState::CreateList(_) => &mut self.list_detail_inputs[self.active_detail_input],
+            AppState::Help(prev) => self.get_state_input(prev),
+            AppState::HelpSearch(_) => &mut self.help_search_input,
             _ => unreachable!()
         }
     }
@@ -631,14 +2287,21 @@ impl App {
 
     pub fn next_detail_input(&mut self) {
         self.active_detail_input += 1;
-        self.active_detail_input %= self.task_detail_inputs.len();
+        self.active_detail_input %= self.num_detail_inputs();
     }
 
     pub fn prev_detail_input(&mut self) {
         let res = self.active_detail_input.checked_sub(1);
         match res {
             Some(i) => self.active_detail_input = i,
-            None => self.active_detail_input = self.task_detail_inputs.len() - 1
+            None => self.active_detail_input = self.num_detail_inputs() - 1
+        }
+    }
+
+    fn num_detail_inputs(&self) -> usize {
+        match self.state {
+            AppState::EditList(_) | AppState::CreateList(_) => self.list_detail_inputs.len(),
+            _ => self.task_detail_inputs.len()
         }
     }
 
@@ -646,29 +2309,67 @@ impl App {
         self.active_detail_input = 0;
     }
 
-    pub fn save_details_to_task(&mut self) {
+    fn reject_detail_input(&mut self, input: usize, message: String) -> bool {
+        self.active_detail_input = input;
+        self.message = Some(message);
+
+        false
+    }
+
+    pub fn save_details_to_task(&mut self) -> bool {
+        // Fields that don't parse keep the editor open on them, with a
+        // message saying why, instead of losing what the task had
+        let due_text = self.task_detail_inputs[3].text.trim().to_string();
+        let due = match NaiveDate::parse_from_str(&due_text, "%Y-%m-%d") {
+            Ok(date) => Some(date),
+            Err(_) if due_text.is_empty() => None,
+            Err(_) => {
+                let message = format!("'{}' isn't a date like 2024-12-31", due_text);
+                return self.reject_detail_input(3, message);
+            }
+        };
+
+        let priority_text = self.task_detail_inputs[4].text.trim().to_string();
+        let priority = match Priority::parse(&priority_text) {
+            Some(priority) => Some(priority),
+            None if priority_text.is_empty() => None,
+            None => {
+                let message = format!("'{}' isn't high, medium, or low", priority_text);
+                return self.reject_detail_input(4, message);
+            }
+        };
+
+        let estimate_text = self.task_detail_inputs[5].text.trim().to_string();
+        let estimate = match estimate_text.parse::<f64>() {
+            Ok(estimate) if estimate.is_finite() && estimate >= 0.0 => Some(estimate),
+            Err(_) if estimate_text.is_empty() => None,
+            _ => {
+                let message = format!(
+                    "'{}' isn't a number of {}",
+                    estimate_text,
+                    self.config.estimate_unit.name().to_lowercase()
+                );
+                return self.reject_detail_input(5, message);
+            }
+        };
+
         let summary = self.task_detail_inputs[0].extract();
         let desc = self.task_detail_inputs[1].extract();
         let cat = self.task_detail_inputs[2].extract();
-
-        let description: Option<String>;
-        if desc == "" {
-            description = None;
-        } else {
-            description = Some(desc);
+        for input in &mut self.task_detail_inputs[3..] {
+            input.clear();
         }
 
-        let category: Option<String>;
-        if cat == "" {
-            category = None;
+        let description = if desc.is_empty() {
+            None
         } else {
-            category = Some(cat);
-        }
+            Some(desc)
+        };
 
-        let new_task = Task {
-            summary,
-            description,
-            category,
+        let category = if cat.is_empty() {
+            None
+        } else {
+            Some(cat)
         };
 
         match self.state.clone() {
@@ -676,14 +2377,29 @@ impl App {
                 let list = self.get_mut_focused_list(&prev);
 
                 if let Some(i) = list.get_selected_index() {
-                    list.remove(i);
-                    list.insert(i, new_task);
+                    list.tasks[i].edit(|task| {
+                        task.summary = summary;
+                        task.description = description;
+                        task.category = category;
+                        task.due = due;
+                        task.priority = priority;
+                        task.estimate = estimate;
+                    });
+                    list.resort();
                 }
             },
             AppState::CreateTask(prev) => {
                 let list = self.get_mut_focused_list(&prev);
 
-                list.push(new_task);
+                let mut task = Task::new(summary);
+                task.description = description;
+                task.category = category;
+                task.due = due;
+                task.priority = priority;
+                task.estimate = estimate;
+                task.record(TaskEvent::Created { list: list.name.clone() });
+
+                list.add(task);
                 if list.len() == 1 {
                     list.select(Some(0));
                 }
@@ -692,6 +2408,8 @@ impl App {
         }
 
         self.unsaved_changes = true;
+
+        true
     }
 
     pub fn clear_detail_inputs(&mut self) {
@@ -708,28 +2426,52 @@ impl App {
     pub fn populate_list_detail_inputs(&mut self) {
         let list = self.get_focused_list(&self.state);
 
-        self.list_detail_input = Input::from(list.name.clone())
+        let wip_limit = match list.wip_limit {
+            Some(limit) => limit.to_string(),
+            None => String::new()
+        };
+        let archive_after_days = match list.archive_after_days {
+            Some(days) => days.to_string(),
+            None => String::new()
+        };
+
+        self.list_detail_inputs[0] = Input::from(list.name.clone())
             .name("List Name".to_string());
+        self.list_detail_inputs[1] = Input::from(wip_limit)
+            .name("WIP Limit".to_string());
+        self.list_detail_inputs[2] = Input::from(archive_after_days)
+            .name("Archive After (Days)".to_string());
     }
 
     pub fn save_details_to_list(&mut self) {
-        let name = self.list_detail_input.extract();
+        let name = self.list_detail_inputs[0].extract();
+        // Anything that isn't a positive number means no limit
+        let wip_limit = self.list_detail_inputs[1]
+            .extract()
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|limit| *limit > 0);
+        // Same for the archive age, which is in whole days
+        let archive_after_days = self.list_detail_inputs[2]
+            .extract()
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|days| *days > 0);
 
         match self.state.clone() {
             AppState::EditList(prev) => {
                 let list = self.get_mut_focused_list(&prev);
-
-                let new_list = TaskList {
-                    name,
-                    color_index: list.color_index,
-                    state: list.state.clone(),
-                    tasks: list.tasks.clone(),
-                };
-
-                self.set_focused_list(&prev, new_list);
+                list.name = name;
+                list.wip_limit = wip_limit;
+                list.archive_after_days = archive_after_days;
             },
             AppState::CreateList(_prev) => {
-                self.task_lists.push(TaskList::from(name));
+                let mut list = TaskList::from(name);
+                list.wip_limit = wip_limit;
+                list.archive_after_days = archive_after_days;
+                self.task_lists.push(list);
             },
             _ => {}
         }
@@ -738,6 +2480,11 @@ impl App {
     }
 
     pub fn delete_highlighted_task(&mut self) {
+        if self.has_marked_tasks() {
+            self.delete_marked_tasks();
+            return;
+        }
+
         if let AppState::DeleteTask(prev) = self.state.clone() {
             let list = self.get_mut_focused_list(&prev);
 
@@ -771,7 +2518,9 @@ impl App {
     }
 
     pub fn clear_list_inputs(&mut self) {
-        self.list_detail_input.clear();
+        for input in self.list_detail_inputs.iter_mut() {
+            input.clear();
+        }
     }
 
     pub fn input_left(&mut self) {
@@ -816,7 +2565,7 @@ impl App {
     pub fn save_to_project(&mut self) -> Result<(), std::io::Error> {
         let name = self.project_detail_input.extract();
 
-        if name == "" {
+        if name.is_empty() {
             return Ok(());
         }
 
@@ -866,6 +2615,58 @@ impl App {
         Ok(())
     }
 
+    pub fn open_help(&mut self) {
+        self.help_scroll = 0;
+        self.help_search_input.clear();
+        self.change_state(AppState::Help(Box::new(self.state.clone())));
+    }
+
+    pub fn get_help_entries(&self) -> Vec<(String, &'static str)> {
+        let mode = match &self.state {
+            AppState::Help(prev) => Mode::from(&**prev),
+            AppState::HelpSearch(prev) => match &**prev {
+                AppState::Help(prev) => Mode::from(&**prev),
+                _ => unreachable!()
+            },
+            _ => unreachable!()
+        };
+
+        // Collect every key bound to an action onto a single line, keeping
+        // the order in which the actions first appear in the keymap
+        let mut entries: Vec<(Vec<String>, Action)> = Vec::new();
+        for binding in self.config.keymap.bindings(mode) {
+            match entries.iter_mut().find(|(_, action)| *action == binding.action) {
+                Some((keys, _)) => keys.push(binding.key.to_string()),
+                None => entries.push((vec![binding.key.to_string()], binding.action)),
+            }
+        }
+
+        let search = self.help_search_input.text.to_lowercase();
+
+        entries
+            .into_iter()
+            .map(|(keys, action)| (keys.join(" / "), action.description()))
+            .filter(|(keys, description)| {
+                keys.to_lowercase().contains(&search)
+                    || description.contains(&search)
+            })
+            .collect()
+    }
+
+    pub fn scroll_help(&mut self, amount: i16) {
+        let max_scroll = self.get_help_entries().len().saturating_sub(1) as i16;
+        let new_scroll = self.help_scroll as i16 + amount;
+        self.help_scroll = new_scroll.clamp(0, max_scroll) as u16;
+    }
+
+    pub fn scroll_help_to_top(&mut self) {
+        self.help_scroll = 0;
+    }
+
+    pub fn scroll_help_to_bottom(&mut self) {
+        self.help_scroll = self.get_help_entries().len().saturating_sub(1) as u16;
+    }
+
     pub fn populate_project_detail_inputs(&mut self) {
         if let Some(i) = self.project_list.get_selected_index() {
             let project = &self.project_list.projects[i];
@@ -875,12 +2676,26 @@ impl App {
     }
 }
 
-fn read_tracker_file() -> Result<Vec<TaskList>, std::io::Error> {
-    let mut path = env::current_dir()?;
-    path.push(TRACKER_FILE);
+fn file_task_between(task: &mut Task, source: &ListOrigin, dest: &ListOrigin) {
+    // Keeps a task's archive details and history in step with its move
+    // from one list to another
+    let from = &source.name;
+    let to = &dest.name;
+    task.moved_in = Some(Local::now());
+
+    match (source.list, dest.list) {
+        (_, ListRef::Archive) => task.archive_from(from),
+        (ListRef::Archive, _) => task.restore_to(to),
+        (_, ListRef::Backlog) => task.record(TaskEvent::Backlogged { from: from.clone() }),
+        _ => task.record(TaskEvent::Moved { from: from.clone(), to: to.clone() }),
+    }
+}
+
+fn read_tracker_file(dir: &Path) -> Result<Vec<TaskList>, std::io::Error> {
+    let path = dir.join(TRACKER_FILE);
 
     if !path.exists() {
-        save_tracker_file(&vec![TaskList::default()])?;
+        save_tracker_file(dir, &vec![TaskList::default()])?;
     }
 
     let file_contents = fs::read_to_string(path.as_path())?;
@@ -888,12 +2703,11 @@ fn read_tracker_file() -> Result<Vec<TaskList>, std::io::Error> {
     Ok(parsed)
 }
 
-fn read_backlog_file() -> Result<TaskList, std::io::Error> {
-    let mut path = env::current_dir()?;
-    path.push(BACKLOG_FILE);
+fn read_backlog_file(dir: &Path) -> Result<TaskList, std::io::Error> {
+    let path = dir.join(BACKLOG_FILE);
 
     if !path.exists() {
-        save_backlog_file(&TaskList::empty_backlog())?;
+        save_backlog_file(dir, &TaskList::empty_backlog())?;
     }
 
     let file_contents = fs::read_to_string(path.as_path())?;
@@ -901,12 +2715,11 @@ fn read_backlog_file() -> Result<TaskList, std::io::Error> {
     Ok(parsed)
 }
 
-fn read_archive_file() -> Result<TaskList, std::io::Error> {
-    let mut path = env::current_dir()?;
-    path.push(ARCHIVE_FILE);
+fn read_archive_file(dir: &Path) -> Result<TaskList, std::io::Error> {
+    let path = dir.join(ARCHIVE_FILE);
 
     if !path.exists() {
-        save_archive_file(&TaskList::empty_archive())?;
+        save_archive_file(dir, &TaskList::empty_archive())?;
     }
 
     let file_contents = fs::read_to_string(path.as_path())?;
@@ -914,26 +2727,403 @@ fn read_archive_file() -> Result<TaskList, std::io::Error> {
     Ok(parsed)
 }
 
-fn save_tracker_file(data: &Vec<TaskList>) -> Result<(), std::io::Error> {
-    let mut path = env::current_dir()?;
-    path.push(TRACKER_FILE);
+// Reads another project's files without creating any that are missing,
+// since that project isn't open
+fn peek_tracker_file(dir: &Path) -> Result<Vec<TaskList>, std::io::Error> {
+    if !dir.join(TRACKER_FILE).exists() {
+        return Ok(vec![TaskList::default()]);
+    }
+    read_tracker_file(dir)
+}
+
+fn peek_backlog_file(dir: &Path) -> Result<TaskList, std::io::Error> {
+    if !dir.join(BACKLOG_FILE).exists() {
+        return Ok(TaskList::empty_backlog());
+    }
+    read_backlog_file(dir)
+}
+
+fn save_tracker_file(dir: &Path, data: &Vec<TaskList>) -> Result<(), std::io::Error> {
+    let path = dir.join(TRACKER_FILE);
     let json_data = serde_json::to_string_pretty(data)?;
     fs::write(path.as_path(), json_data)?;
     Ok(())
 }
 
-fn save_backlog_file(data: &TaskList) -> Result<(), std::io::Error> {
-    let mut path = env::current_dir()?;
-    path.push(BACKLOG_FILE);
+fn save_backlog_file(dir: &Path, data: &TaskList) -> Result<(), std::io::Error> {
+    let path = dir.join(BACKLOG_FILE);
     let json_data = serde_json::to_string_pretty(data)?;
     fs::write(path.as_path(), json_data)?;
     Ok(())
 }
 
-fn save_archive_file(data: &TaskList) -> Result<(), std::io::Error> {
-    let mut path = env::current_dir()?;
-    path.push(ARCHIVE_FILE);
+fn save_archive_file(dir: &Path, data: &TaskList) -> Result<(), std::io::Error> {
+    let path = dir.join(ARCHIVE_FILE);
+    let json_data = serde_json::to_string_pretty(data)?;
+    fs::write(path.as_path(), json_data)?;
+    Ok(())
+}
+
+fn get_archive_year_path(dir: &Path, year: i32) -> PathBuf {
+    dir.join(format!("archive-{}.json", year))
+}
+
+fn get_archive_years(dir: &Path) -> Result<Vec<i32>, std::io::Error> {
+    // Every year with a rotated archive file, newest first
+    let mut years = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        let year = name
+            .strip_prefix("archive-")
+            .and_then(|name| name.strip_suffix(".json"))
+            .and_then(|year| year.parse::<i32>().ok());
+
+        if let Some(year) = year {
+            years.push(year);
+        }
+    }
+    years.sort_by(|a, b| b.cmp(a));
+
+    Ok(years)
+}
+
+fn read_archive_year_file(dir: &Path, year: i32) -> Result<Vec<Task>, std::io::Error> {
+    let path = get_archive_year_path(dir, year);
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file_contents = fs::read_to_string(path.as_path())?;
+    let parsed: Vec<Task> = serde_json::from_str(&file_contents)?;
+    Ok(parsed)
+}
+
+fn save_archive_year_file(dir: &Path, year: i32, data: &Vec<Task>) -> Result<(), std::io::Error> {
+    let path = get_archive_year_path(dir, year);
     let json_data = serde_json::to_string_pretty(data)?;
     fs::write(path.as_path(), json_data)?;
     Ok(())
 }
+
+fn get_snapshot_path(dir: &Path, date: NaiveDate) -> PathBuf {
+    dir.join(HISTORY_DIR).join(format!("{}.json", date.format("%Y-%m-%d")))
+}
+
+fn get_snapshot_dates(dir: &Path) -> Result<Vec<NaiveDate>, std::io::Error> {
+    // Every day with a snapshot, oldest first
+    let path = dir.join(HISTORY_DIR);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut dates = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        let date = name
+            .strip_suffix(".json")
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+
+        if let Some(date) = date {
+            dates.push(date);
+        }
+    }
+    dates.sort();
+
+    Ok(dates)
+}
+
+fn read_snapshot_file(dir: &Path, date: NaiveDate) -> Result<Snapshot, std::io::Error> {
+    let path = get_snapshot_path(dir, date);
+    let file_contents = fs::read_to_string(path.as_path())?;
+    let parsed: Snapshot = serde_json::from_str(&file_contents)?;
+    Ok(parsed)
+}
+
+fn save_snapshot_file(dir: &Path, date: NaiveDate, data: &Snapshot) -> Result<(), std::io::Error> {
+    fs::create_dir_all(dir.join(HISTORY_DIR))?;
+    let path = get_snapshot_path(dir, date);
+    // Kept compact, since there's one for every day the project is used
+    let json_data = serde_json::to_string(data)?;
+    fs::write(path.as_path(), json_data)?;
+    Ok(())
+}
+
+pub fn report_changes(
+    project: &str,
+    since: NaiveDate,
+    until: Option<NaiveDate>
+) -> Result<String, std::io::Error> {
+    // Compares two of the project's snapshots, listing what was added,
+    // moved, archived or removed between them
+    let dir = env::current_dir()?.join(project);
+    if project.is_empty() || !dir.exists() {
+        return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Project '{}' does not exist.", project)
+            )
+        );
+    }
+
+    // The report ends on the last snapshot up to its end date, or the latest
+    // one, and starts on the last one from before its start, or else the first
+    let dates = get_snapshot_dates(&dir)?;
+    let end = match dates.iter().rev().find(|date| until.is_none_or(|until| **date <= until)) {
+        Some(date) => *date,
+        None => return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                match until {
+                    Some(until) => format!(
+                        "'{}' has no snapshots up to {}",
+                        project,
+                        until.format("%Y-%m-%d")
+                    ),
+                    None => format!(
+                        "'{}' has no snapshots yet, one is taken on the first save of each day",
+                        project
+                    ),
+                }
+            )
+        )
+    };
+    let start = dates
+        .iter()
+        .rev()
+        .find(|date| **date <= cmp::min(since, end))
+        .unwrap_or(&dates[0]);
+    let old = read_snapshot_file(&dir, *start)?;
+    let new = read_snapshot_file(&dir, end)?;
+
+    let mut summaries: HashMap<u64, String> = HashMap::new();
+    for task in read_all_tasks(&dir)? {
+        if let Some(id) = task.id {
+            summaries.insert(id, task.summary);
+        }
+    }
+    let summary = |id: &u64| match summaries.get(id) {
+        Some(summary) => format!("'{}'", summary),
+        None => "A deleted task".to_string()
+    };
+    let old_name = |list_ref: ListRef| old.get_list(list_ref).map_or("", |list| list.name.as_str());
+
+    let before = old.locations();
+    let after = new.locations();
+
+    let mut added = Vec::new();
+    let mut moved = Vec::new();
+    let mut archived = Vec::new();
+    for (list_ref, list) in new.all_lists() {
+        for id in &list.tasks {
+            match before.get(id) {
+                None => added.push(format!("{} in '{}'", summary(id), list.name)),
+                Some(from) if *from == list_ref => {},
+                Some(from) if list_ref == ListRef::Archive => {
+                    archived.push(format!("{} from '{}'", summary(id), old_name(*from)));
+                },
+                Some(from) => moved.push(format!(
+                    "{} from '{}' to '{}'",
+                    summary(id),
+                    old_name(*from),
+                    list.name
+                )),
+            }
+        }
+    }
+    let removed: Vec<String> = old
+        .all_lists()
+        .flat_map(|(_, list)| list.tasks.iter().map(move |id| (id, &list.name)))
+        .filter(|(id, _)| !after.contains_key(id))
+        .map(|(id, from)| format!("{} from '{}'", summary(id), from))
+        .collect();
+
+    let mut lines = vec![format!(
+        "Changes in '{}' from the snapshot of {} to that of {}",
+        project,
+        start.format("%Y-%m-%d"),
+        end.format("%Y-%m-%d")
+    )];
+    for (heading, changes) in [
+        ("Added", added),
+        ("Moved", moved),
+        ("Archived", archived),
+        ("Removed", removed),
+    ] {
+        if !changes.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("{} {}:", heading, count_tasks(changes.len())));
+            lines.extend(changes.into_iter().map(|change| format!("  {}", change)));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("List Sizes:".to_string());
+    for (list_ref, list) in new.all_lists() {
+        let old_size = old.get_list(list_ref).map_or(0, |old_list| old_list.size);
+        lines.push(format!("  {}: {} -> {}", list.name, old_size, list.size));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+pub fn export_timesheet(project: &str, since: NaiveDate, csv: bool) -> Result<String, std::io::Error> {
+    // Time spent on each task per day, as a readable list or as CSV
+    let dir = env::current_dir()?.join(project);
+    if project.is_empty() || !dir.exists() {
+        return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Project '{}' does not exist.", project)
+            )
+        );
+    }
+
+    let mut days: BTreeMap<NaiveDate, Vec<(Task, TimeDelta)>> = BTreeMap::new();
+    for task in read_all_tasks(&dir)? {
+        for (day, time) in task.time_by_day() {
+            if day >= since {
+                days.entry(day).or_default().push((task.clone(), time));
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    if csv {
+        lines.push("date,task,category,minutes".to_string());
+        for (day, entries) in &days {
+            for (task, time) in entries {
+                lines.push(format!(
+                    "{},{},{},{}",
+                    day.format("%Y-%m-%d"),
+                    csv_field(&task.summary),
+                    csv_field(task.category.as_deref().unwrap_or_default()),
+                    time.num_minutes()
+                ));
+            }
+        }
+    } else {
+        lines.push(format!("Timesheet for '{}' since {}", project, since.format("%Y-%m-%d")));
+        if days.is_empty() {
+            lines.push(String::new());
+            lines.push("No time was tracked".to_string());
+        }
+        for (day, entries) in &days {
+            lines.push(String::new());
+            lines.push(day.format("%Y-%m-%d %A").to_string());
+            for (task, time) in entries {
+                lines.push(format!("  {:>8}  {}", format_time_spent(*time), task.summary));
+            }
+            let total: TimeDelta = entries.iter().map(|(_, time)| *time).sum();
+            lines.push(format!("  {:>8}  Total", format_time_spent(total)));
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn read_all_tasks(dir: &Path) -> Result<Vec<Task>, std::io::Error> {
+    // Every task in the project, including those rotated out of the archive
+    let mut tasks = Vec::new();
+    for list in read_tracker_file(dir)? {
+        tasks.extend(list.tasks);
+    }
+    tasks.extend(read_backlog_file(dir)?.tasks);
+    tasks.extend(read_archive_file(dir)?.tasks);
+    for year in get_archive_years(dir)? {
+        tasks.extend(read_archive_year_file(dir, year)?);
+    }
+
+    Ok(tasks)
+}
+
+pub fn prune_archive(project: &str, config: &Config) -> Result<String, std::io::Error> {
+    // Applies the retention settings to a project's archive, either
+    // deleting the old tasks or rotating them into a file per year
+    if config.archive_keep.is_none() && config.archive_max_age_days.is_none() {
+        return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Set archive_keep or archive_max_age_days in config.json to prune archives"
+            )
+        );
+    }
+
+    let dir = env::current_dir()?.join(project);
+    if project.is_empty() || !dir.exists() {
+        return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Project '{}' does not exist.", project)
+            )
+        );
+    }
+
+    // An open project would write its whole archive back on its next save
+    if dir.join(LOCK_FILE).exists() {
+        return Err(std::io::Error::new(
+                std::io::ErrorKind::WouldBlock,
+                format!(
+                    "'{}' is open in kadai, so close it before pruning, or delete {} if it isn't",
+                    project,
+                    dir.join(LOCK_FILE).display()
+                )
+            )
+        );
+    }
+
+    let mut archive = read_archive_file(&dir)?;
+    let old_tasks = archive.take_old_tasks(config.archive_keep, config.archive_max_age_days);
+    if old_tasks.is_empty() {
+        return Ok(format!("Nothing to prune in '{}'", project));
+    }
+
+    let summary = if config.archive_rotate {
+        let mut years: BTreeMap<i32, Vec<Task>> = BTreeMap::new();
+        for task in old_tasks {
+            let year = task.archived_at.map_or(0, |archived_at| archived_at.year());
+            years.entry(year).or_default().push(task);
+        }
+
+        // The year files are written before the archive shrinks, so nothing
+        // is lost if writing one of them fails
+        let mut moved = Vec::new();
+        for (year, tasks) in years {
+            moved.push(format!("{} to archive-{}.json", count_tasks(tasks.len()), year));
+
+            let mut year_tasks = read_archive_year_file(&dir, year)?;
+            year_tasks.extend(tasks);
+            save_archive_year_file(&dir, year, &year_tasks)?;
+        }
+
+        format!("Moved {} in '{}'", moved.join(", "), project)
+    } else {
+        format!("Deleted {} from the archive of '{}'", count_tasks(old_tasks.len()), project)
+    };
+    save_archive_file(&dir, &archive)?;
+
+    Ok(summary)
+}
+
+fn get_position(tasks: &[usize], selected: Option<usize>) -> Option<usize> {
+    selected.and_then(|i| tasks.iter().position(|task| *task == i))
+}
+
+fn get_project_dir(project: &str) -> Result<PathBuf, std::io::Error> {
+    // Projects sit side by side, and we're always inside the open one
+    let mut path = env::current_dir()?;
+    path.pop();
+    path.push(project);
+
+    Ok(path)
+}
+
+pub fn count_tasks(count: usize) -> String {
+    match count {
+        1 => "1 Task".to_string(),
+        n => format!("{} Tasks", n)
+    }
+}
@@ -0,0 +1,72 @@
+use crate::lists::*;
+
+pub const COMMAND_NAMES: [&str; 12] = [
+    "archive",
+    "autosort",
+    "backlog",
+    "filter",
+    "help",
+    "move",
+    "project",
+    "q",
+    "q!",
+    "sort",
+    "w",
+    "wq",
+];
+
+pub enum Command {
+    Write,
+    Quit,
+    ForceQuit,
+    WriteQuit,
+    Archive,
+    Backlog,
+    Move(String),
+    Sort(SortOrder),
+    AutoSort(Option<SortOrder>),
+    Filter(Option<TaskFilter>),
+    Project(String),
+    Help,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let (name, args) = match line.split_once(' ') {
+            Some((name, args)) => (name, args.trim()),
+            None => (line, "")
+        };
+
+        let command = match name {
+            "w" | "write" => Command::Write,
+            "q" | "quit" => Command::Quit,
+            "q!" | "quit!" => Command::ForceQuit,
+            "wq" | "x" => Command::WriteQuit,
+            "archive" => Command::Archive,
+            "backlog" => Command::Backlog,
+            "help" => Command::Help,
+            "filter" => Command::Filter(TaskFilter::parse(args)),
+            "move" if !args.is_empty() => Command::Move(args.to_string()),
+            "project" if !args.is_empty() => Command::Project(args.to_string()),
+            "sort" => match SortOrder::parse(args) {
+                Some(order) => Command::Sort(order),
+                None => return Err(sort_usage())
+            },
+            "autosort" if args == "off" => Command::AutoSort(None),
+            "autosort" => match SortOrder::parse(args) {
+                Some(order) => Command::AutoSort(Some(order)),
+                None => return Err(sort_usage())
+            },
+            "move" | "project" => return Err(format!("'{}' needs a name", name)),
+            "" => return Err("No command given".to_string()),
+            _ => return Err(format!("Unknown command '{}'", name))
+        };
+
+        Ok(command)
+    }
+}
+
+fn sort_usage() -> String {
+    format!("Sort by {}, then optionally asc or desc", SortKey::NAMES.join(", "))
+}
@@ -0,0 +1,81 @@
+use serde::Deserialize;
+use std::{collections::HashMap, env, fs, io};
+
+use crate::keymap::*;
+use crate::lists::*;
+
+const CONFIG_FILE: &str = "config.json";
+
+#[derive(Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WipLimitPolicy {
+    #[default]
+    Confirm,
+    Refuse,
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub mouse: bool,
+    pub columns: Option<usize>,
+    pub min_column_width: u16,
+    pub scroll_off: usize,
+    pub wip_limit_policy: WipLimitPolicy,
+    pub archive_keep: Option<usize>,
+    pub archive_max_age_days: Option<u32>,
+    pub archive_rotate: bool,
+    pub estimate_unit: EstimateUnit,
+    keys: HashMap<Mode, HashMap<String, Option<Action>>>,
+    #[serde(skip)]
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            mouse: true,
+            columns: None,
+            min_column_width: 30,
+            scroll_off: 2,
+            wip_limit_policy: WipLimitPolicy::default(),
+            archive_keep: None,
+            archive_max_age_days: None,
+            archive_rotate: false,
+            estimate_unit: EstimateUnit::default(),
+            keys: HashMap::new(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Result<Self, io::Error> {
+        let mut path = env::current_dir()?;
+        path.push(CONFIG_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file_contents = fs::read_to_string(path.as_path())?;
+        let mut config: Config = serde_json::from_str(&file_contents)?;
+
+        for (mode, bindings) in &config.keys {
+            for (key, action) in bindings {
+                match Key::parse(key) {
+                    Some(key) => config.keymap.bind(*mode, key, *action),
+                    None => {
+                        return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("Invalid key '{}' in {}", key, CONFIG_FILE)
+                            )
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
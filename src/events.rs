@@ -4,48 +4,222 @@ use crossterm::{
         Event,
         KeyCode,
         KeyEvent,
-        KeyEventKind,
-        KeyEventState,
-        KeyModifiers
+        MouseButton,
+        MouseEvent,
+        MouseEventKind
     }
 };
 use std::io;
+use tui::layout::Rect;
 
 use crate::app::*;
+use crate::keymap::*;
 
 pub fn handle_events(app: &mut App) -> io::Result<()> {
-    if let Event::Key(key) = event::read()? {
-        let state = app.state.clone();
-        match state {
-            AppState::ProjectMenu => handle_project_menu_events(key, app, state)?,
-            AppState::EditProject(prev) => handle_edit_project_events(key, app, *prev)?,
-            AppState::CreateProject(prev) => handle_create_project_events(key, app, *prev)?,
-            AppState::DeleteProject(prev) => handle_delete_project_events(key, app, *prev)?,
-            AppState::Tracker => handle_tracker_events(key, app, state)?,
-            AppState::TaskView(prev) => handle_task_view_events(key, app, *prev)?,
-            AppState::BacklogPopup(prev) => handle_backlog_popup_events(key, app, *prev)?,
-            AppState::ArchivePopup(prev) => handle_archive_popup_events(key, app, *prev)?,
-            AppState::EditTask(prev) => handle_edit_task_events(key, app, *prev),
-            AppState::CreateTask(prev) => handle_create_task_events(key, app, *prev),
-            AppState::DeleteTask(prev) => handle_delete_task_events(key, app, *prev),
-            AppState::EditList(prev) => handle_edit_list_events(key, app, *prev),
-            AppState::CreateList(prev) => handle_create_list_events(key, app, *prev),
-            AppState::DeleteList(prev) => handle_delete_list_events(key, app, *prev),
+    // Waiting for input gives up now and then, so that tasks still get
+    // archived on time while kadai sits idle, and a running timer keeps
+    // counting up on screen
+    let timeout = if app.get_timed_task().is_some() {
+        TIMER_TICK
+    } else {
+        AUTO_ARCHIVE_INTERVAL
+    };
+    if event::poll(timeout)? {
+        match event::read()? {
+            Event::Key(key) => handle_key_events(key, app)?,
+            Event::Mouse(mouse) => handle_mouse_events(mouse, app),
+            _ => {}
         }
     }
+    app.check_auto_archive();
+
+    if let AppState::Tracker = app.state {
+        app.sync_lane_focus();
+    }
+
+    Ok(())
+}
+
+fn handle_key_events(key: KeyEvent, app: &mut App) -> io::Result<()> {
+    app.message = None;
+
+    let state = app.state.clone();
+    match state {
+        AppState::ProjectMenu => handle_project_menu_events(key, app, state)?,
+        AppState::EditProject(prev) => handle_edit_project_events(key, app, *prev)?,
+        AppState::CreateProject(prev) => handle_create_project_events(key, app, *prev)?,
+        AppState::DeleteProject(prev) => handle_delete_project_events(key, app, *prev)?,
+        AppState::Tracker => handle_tracker_events(key, app, state)?,
+        AppState::TaskView(prev) => handle_task_view_events(key, app, *prev)?,
+        AppState::BacklogPopup(prev) => handle_backlog_popup_events(key, app, *prev)?,
+        AppState::ArchivePopup(prev) => handle_archive_popup_events(key, app, *prev)?,
+        AppState::ArchiveSearch(prev) => handle_archive_search_events(key, app, *prev),
+        AppState::EditTask(prev) => handle_edit_task_events(key, app, *prev),
+        AppState::CreateTask(prev) => handle_create_task_events(key, app, *prev),
+        AppState::DeleteTask(prev) => handle_delete_task_events(key, app, *prev),
+        AppState::SetCategory(prev) => handle_set_category_events(key, app, *prev),
+        AppState::SendToProject(prev) => handle_send_to_project_events(key, app, *prev),
+        AppState::SendToProjectList(prev) => handle_send_to_project_list_events(key, app, *prev),
+        AppState::MoveToList(prev) => handle_move_to_list_events(key, app, *prev),
+        AppState::CommandLine(prev) => handle_command_line_events(key, app, *prev),
+        AppState::EditList(prev) => handle_edit_list_events(key, app, *prev),
+        AppState::CreateList(prev) => handle_create_list_events(key, app, *prev),
+        AppState::DeleteList(prev) => handle_delete_list_events(key, app, *prev),
+        AppState::ConfirmMove(prev) => handle_confirm_move_events(key, app, *prev),
+        AppState::Statistics(prev) => handle_statistics_events(key, app, *prev),
+        AppState::FlowCharts(prev) => handle_flow_charts_events(key, app, *prev),
+        AppState::SetBurndownTarget(prev) => handle_set_burndown_target_events(key, app, *prev),
+        AppState::Help(prev) => handle_help_events(key, app, *prev),
+        AppState::HelpSearch(prev) => handle_help_search_events(key, app, *prev),
+    }
 
     Ok(())
 }
 
+fn handle_mouse_events(mouse: MouseEvent, app: &mut App) {
+    let state = app.state.clone();
+    match state {
+        AppState::ProjectMenu => {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => app.list_down(),
+                MouseEventKind::ScrollUp => app.list_up(),
+                _ => {}
+            }
+        },
+        AppState::Tracker => handle_tracker_mouse_events(mouse, app, state),
+        AppState::TaskView(_) => {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => app.scroll_details(1),
+                MouseEventKind::ScrollUp => app.scroll_details(-1),
+                _ => {}
+            }
+        },
+        AppState::BacklogPopup(_) => handle_list_popup_mouse_events(mouse, app, state),
+        AppState::ArchivePopup(_) => handle_list_popup_mouse_events(mouse, app, state),
+        AppState::Help(_) => {
+            match mouse.kind {
+                MouseEventKind::ScrollDown => app.scroll_help(1),
+                MouseEventKind::ScrollUp => app.scroll_help(-1),
+                _ => {}
+            }
+        },
+        _ => {}
+    }
+}
+
+fn handle_tracker_mouse_events(mouse: MouseEvent, app: &mut App, state: AppState) {
+    let (column, row) = (mouse.column, mouse.row);
+    let list = app.list_areas
+        .iter()
+        .find(|(_, area)| area_contains(area, column, row))
+        .map(|(list, _)| *list);
+    let lane = app.lane_areas
+        .iter()
+        .find(|(_, area)| area_contains(area, column, row))
+        .map(|(lane, _)| *lane);
+    let card = app.card_areas
+        .iter()
+        .find(|(_, _, area)| area_contains(area, column, row))
+        .map(|(list, task, _)| (*list, *task));
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(i) = list {
+                app.focus_list(i);
+            }
+            if let Some(i) = lane {
+                app.focus_lane(i);
+            }
+            if let Some((_, task)) = card {
+                app.focus_task(task);
+                if app.register_click(column, row) {
+                    app.change_state(AppState::TaskView(Box::new(state)));
+                } else {
+                    app.drag = card;
+                }
+            }
+        },
+        MouseEventKind::Drag(MouseButton::Left)
+            if app.drag.is_some() => {
+                app.drag_over = list;
+            },
+        MouseEventKind::Up(MouseButton::Left) => {
+            if let (Some(from), Some(dest)) = (app.drag, list) {
+                // Dropping onto a card takes its place, anywhere else in the
+                // list puts the task at the bottom
+                let to = match card {
+                    Some(card) => card,
+                    None => (dest, app.task_lists[dest].len())
+                };
+                app.request_task_move(TaskMove::Drop(from, to));
+            }
+            app.drag = None;
+            app.drag_over = None;
+        },
+        MouseEventKind::ScrollDown => {
+            if let Some(i) = list {
+                app.focus_list(i);
+                if let Some(i) = lane {
+                    app.focus_lane(i);
+                }
+                app.list_down();
+            }
+        },
+        MouseEventKind::ScrollUp => {
+            if let Some(i) = list {
+                app.focus_list(i);
+                if let Some(i) = lane {
+                    app.focus_lane(i);
+                }
+                app.list_up();
+            }
+        },
+        _ => {}
+    }
+}
+
+fn handle_list_popup_mouse_events(mouse: MouseEvent, app: &mut App, state: AppState) {
+    let (column, row) = (mouse.column, mouse.row);
+    let card = app.popup_card_areas
+        .iter()
+        .find(|(_, area)| area_contains(area, column, row))
+        .map(|(task, _)| *task);
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(task) = card {
+                app.focus_task(task);
+                if app.register_click(column, row) {
+                    app.change_state(AppState::TaskView(Box::new(state)));
+                }
+            }
+        },
+        MouseEventKind::ScrollDown => app.list_down(),
+        MouseEventKind::ScrollUp => app.list_up(),
+        _ => {}
+    }
+}
+
+fn area_contains(area: &Rect, column: u16, row: u16) -> bool {
+    column >= area.left()
+        && column < area.right()
+        && row >= area.top()
+        && row < area.bottom()
+}
+
 fn handle_project_menu_events(
     key: KeyEvent,
     app: &mut App,
     state: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char('q') => app.set_quit(true),
-        KeyCode::Esc => app.set_quit(true),
-        KeyCode::Char('n') => {
+    let action = match app.config.keymap.action(Mode::ProjectMenu, key) {
+        Some(action) => action,
+        None => return Ok(())
+    };
+
+    match action {
+        Action::Quit => app.set_quit(true),
+        Action::NewProject => {
             app.clear_project_inputs();
             app.change_state(
                 AppState::CreateProject(
@@ -53,92 +227,74 @@ fn handle_project_menu_events(
                 )
             );
         },
-        KeyCode::Char('d') => {
-            if !app.project_list.is_empty() {
+        Action::DeleteProject
+            if !app.project_list.is_empty() => {
                 app.change_state(
                     AppState::DeleteProject(
                         Box::new(state)
                     )
                 );
-            }
-        },
-        KeyCode::Char('e') => {
-            if !app.project_list.is_empty() {
+            },
+        Action::EditProject
+            if !app.project_list.is_empty() => {
                 app.populate_project_detail_inputs();
                 app.change_state(
                     AppState::EditProject(
                         Box::new(state)
                     )
                 );
-            }
-        },
-        KeyCode::Char('j') => app.list_down(),
-        KeyCode::Down => app.list_down(),
-        KeyCode::Char('k') => app.list_up(),
-        KeyCode::Up => app.list_up(),
-        KeyCode::Char('g') => app.jump_to_list_top(),
-        KeyCode::Home => app.jump_to_list_top(),
-        KeyCode::Char('G') => app.jump_to_list_bottom(),
-        KeyCode::End => app.jump_to_list_bottom(),
-        KeyCode::Enter => {
-            if !app.project_list.is_empty() {
-                app.select_project()?;
-                app.change_state(AppState::Tracker);
-            }
-        },
-        KeyCode::Char(' ') => {
-            if !app.project_list.is_empty() {
+            },
+        Action::FocusDown => app.list_down(),
+        Action::FocusUp => app.list_up(),
+        Action::FocusTop => app.jump_to_list_top(),
+        Action::FocusBottom => app.jump_to_list_bottom(),
+        Action::SelectProject
+            if !app.project_list.is_empty() => {
                 app.select_project()?;
                 app.change_state(AppState::Tracker);
-            }
-        },
+            },
+        Action::Help => app.open_help(),
         _ => {}
     }
 
     Ok(())
 }
 
+fn handle_input_action(action: Action, app: &mut App) {
+    match action {
+        Action::DeleteWord => app.delete_to_prev_space(),
+        Action::DeleteChar => app.delete_from_detail_input(),
+        Action::ClearInput => app.clear_focused_input(),
+        Action::CursorLeft => app.input_left(),
+        Action::CursorRight => app.input_right(),
+        Action::CursorWordLeft => app.input_jump_to_space_left(),
+        Action::CursorWordRight => app.input_jump_to_space_right(),
+        Action::CursorStart => app.input_start(),
+        Action::CursorEnd => app.input_end(),
+        _ => {}
+    }
+}
+
+fn handle_typed_char(key: KeyEvent, app: &mut App) {
+    if let KeyCode::Char(c) = key.code {
+        app.add_to_detail_input(c);
+    }
+}
+
 fn handle_edit_project_events(
     key: KeyEvent,
     app: &mut App,
     prev: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char(c) => {
-            match key {
-                KeyEvent {
-                    code: KeyCode::Char('w'),
-                    modifiers: KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE
-                } => app.delete_to_prev_space(),
-                _ => app.add_to_detail_input(c)
-            }
-        },
-        KeyCode::Backspace => app.delete_from_detail_input(),
-        KeyCode::Delete => app.clear_focused_input(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_left(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_left(),
-                _ => {}
-            }
-        },
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_right(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_right(),
-                _ => {}
-            }
-        },
-        KeyCode::Home => app.input_start(),
-        KeyCode::End => app.input_end(),
-        KeyCode::Enter => {
+    match app.config.keymap.action(Mode::InputEditor, key) {
+        Some(Action::Confirm) => {
             app.save_to_project()?;
             app.change_state(prev);
-        }
-        KeyCode::Esc => app.change_state(prev),
-        _ => {}
+        },
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
     }
 
     Ok(())
@@ -149,42 +305,15 @@ fn handle_create_project_events(
     app: &mut App,
     prev: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char(c) => {
-            match key {
-                KeyEvent {
-                    code: KeyCode::Char('w'),
-                    modifiers: KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE
-                } => app.delete_to_prev_space(),
-                _ => app.add_to_detail_input(c)
-            }
-        },
-        KeyCode::Backspace => app.delete_from_detail_input(),
-        KeyCode::Delete => app.clear_focused_input(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_left(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_left(),
-                _ => {}
-            }
-        },
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_right(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_right(),
-                _ => {}
-            }
-        },
-        KeyCode::Home => app.input_start(),
-        KeyCode::End => app.input_end(),
-        KeyCode::Enter => {
+    match app.config.keymap.action(Mode::InputEditor, key) {
+        Some(Action::Confirm) => {
             app.save_to_project()?;
             app.change_state(prev);
-        }
-        KeyCode::Esc => app.change_state(prev),
-        _ => {}
+        },
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
     }
 
     Ok(())
@@ -195,130 +324,144 @@ fn handle_delete_project_events(
     app: &mut App,
     prev: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char('y') => {
-            app.delete_focused_project()?;
-            app.change_state(prev);
-        },
-        KeyCode::Char('n') => app.change_state(prev),
-        KeyCode::Enter => {
+    match app.config.keymap.action(Mode::Prompt, key) {
+        Some(Action::Confirm) => {
             app.delete_focused_project()?;
             app.change_state(prev);
         },
-        KeyCode::Esc => app.change_state(prev),
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
         _ => {}
     }
 
     Ok(())
 }
 
+fn get_counted_action(mode: Mode, key: KeyEvent, app: &mut App) -> Option<(Action, usize)> {
+    let action = match app.config.keymap.action(mode, key) {
+        Some(action) => action,
+        None => {
+            // Unbound digits build up a count for the next action
+            if let KeyCode::Char(c) = key.code {
+                if let Some(digit) = c.to_digit(10) {
+                    app.push_count_digit(digit as usize);
+                }
+            }
+            return None;
+        }
+    };
+
+    // Quitting or closing while a count is pending just drops the count
+    if app.count.is_some() && matches!(action, Action::Quit | Action::Close) {
+        app.count = None;
+        return None;
+    }
+
+    Some((action, app.take_count()))
+}
+
 fn handle_tracker_events(
     key: KeyEvent,
     app: &mut App,
     state: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char('q') => app.set_quit(true),
-        KeyCode::Esc => app.set_quit(true),
-        KeyCode::Char('s') => app.save_changes()?,
-        KeyCode::Char('o') => {
+    let counted = app.count.is_some();
+    let (action, count) = match get_counted_action(Mode::Tracker, key, app) {
+        Some(action) => action,
+        None => return Ok(())
+    };
+
+    match action {
+        Action::Quit => app.set_quit(true),
+        Action::Save => app.save_changes()?,
+        Action::OpenProjectMenu => {
             app.save_changes()?;
             app.move_up_one_directory()?;
             app.change_state(AppState::ProjectMenu);
         },
-        KeyCode::Char('n') => {
+        Action::NewTask => {
             app.clear_detail_inputs();
+            app.prefill_lane_category();
             app.reset_active_detail_input();
             app.change_state(AppState::CreateTask(Box::new(state)));
         },
-        KeyCode::Char('d') => {
-            if !app.focused_list_is_empty() {
-                app.change_state(AppState::DeleteTask(Box::new(state)));
-            }
-        },
-        KeyCode::Char('e') => {
-            if !app.focused_list_is_empty() {
+        Action::DeleteTask => app.apply_change(Change::Delete),
+        Action::EditTask
+            if !app.focused_list_is_empty() => {
                 app.populate_task_detail_inputs();
                 app.reset_active_detail_input();
                 app.change_state(AppState::EditTask(Box::new(state)));
-            }
-        },
-        KeyCode::Char('N') => {
+            },
+        Action::NewList => {
             app.clear_list_inputs();
+            app.reset_active_detail_input();
             app.change_state(AppState::CreateList(Box::new(state)));
         },
-        KeyCode::Char('D') => {
+        Action::DeleteList => {
             app.change_state(AppState::DeleteList(Box::new(state)));
         },
-        KeyCode::Char('E') => {
+        Action::EditList => {
             app.populate_list_detail_inputs();
+            app.reset_active_detail_input();
             app.change_state(AppState::EditList(Box::new(state)));
         },
-        KeyCode::Char('j') => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_down(),
-                KeyModifiers::CONTROL => app.task_to_bottom(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('J') => app.task_down(),
-        KeyCode::Down => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_down(),
-                KeyModifiers::CONTROL => app.task_down(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('k') => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_up(),
-                KeyModifiers::CONTROL => app.task_to_top(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('K') => app.task_up(),
-        KeyCode::Up => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_up(),
-                KeyModifiers::CONTROL => app.task_up(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('h') => app.prev_list(),
-        KeyCode::Char('H') => app.list_left(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.prev_list(),
-                KeyModifiers::CONTROL => app.list_left(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('l') => app.next_list(),
-        KeyCode::Char('L') => app.list_right(),
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.next_list(),
-                KeyModifiers::CONTROL => app.list_right(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('g') => app.jump_to_list_top(),
-        KeyCode::Home => app.jump_to_list_top(),
-        KeyCode::Char('G') => app.jump_to_list_bottom(),
-        KeyCode::End => app.jump_to_list_bottom(),
-        KeyCode::Char('c') => app.cycle_list_color(1),
-        KeyCode::Char('C') => app.cycle_list_color(-1),
-        KeyCode::Char(' ') => app.move_task_to_next_list(),
-        KeyCode::Backspace => app.move_task_to_prev_list(),
-        KeyCode::Enter => {
-            if !app.focused_list_is_empty() {
+        Action::FocusDown => (0..count).for_each(|_| app.list_down()),
+        Action::FocusUp => (0..count).for_each(|_| app.list_up()),
+        Action::MoveTaskDown => app.apply_change(Change::TaskDown(count)),
+        Action::MoveTaskUp => app.apply_change(Change::TaskUp(count)),
+        Action::MoveTaskToBottom => app.apply_change(Change::TaskToBottom),
+        Action::MoveTaskToTop => app.apply_change(Change::TaskToTop),
+        Action::FocusPrevList => (0..count).for_each(|_| app.prev_list()),
+        Action::FocusNextList => (0..count).for_each(|_| app.next_list()),
+        Action::FocusNextLane => app.next_lane(),
+        Action::FocusPrevLane => app.prev_lane(),
+        Action::MoveListLeft => (0..count).for_each(|_| app.list_left()),
+        Action::MoveListRight => (0..count).for_each(|_| app.list_right()),
+        Action::ToggleCollapse => app.toggle_list_collapse(),
+        // Like vim's {count}gt for tabs, a count jumps to that list
+        Action::FocusTop if counted => app.focus_list(count - 1),
+        Action::FocusTop => app.jump_to_list_top(),
+        Action::FocusBottom => app.jump_to_list_bottom(),
+        Action::PageDown => app.page_down(),
+        Action::PageUp => app.page_up(),
+        Action::HalfPageDown => app.half_page_down(),
+        Action::HalfPageUp => app.half_page_up(),
+        Action::NextListColor => app.cycle_list_color(1),
+        Action::PrevListColor => app.cycle_list_color(-1),
+        Action::CycleCardMode => app.cycle_card_mode(),
+        Action::CycleAllCardModes => app.cycle_all_card_modes(),
+        Action::ToggleMark => app.toggle_mark(),
+        Action::MarkRange => app.mark_range(),
+        Action::ClearMarks => app.clear_marks(),
+        Action::YankTask => app.yank_tasks(),
+        Action::CutTask => app.cut_tasks(),
+        Action::PasteBelow => app.apply_change(Change::Paste(false, count)),
+        Action::PasteAbove => app.apply_change(Change::Paste(true, count)),
+        Action::RepeatChange => app.repeat_change(count),
+        Action::SendToProject => app.open_project_picker(false),
+        Action::MoveTaskToChosenList => app.open_list_picker(),
+        Action::OpenCommandLine => app.open_command_line(),
+        Action::CopyToProject => app.open_project_picker(true),
+        Action::ToggleSwimlanes => app.toggle_swimlanes(),
+        Action::MoveTaskToNextList => app.apply_change(Change::Move(TaskMove::NextList(count))),
+        Action::MoveTaskToPrevList => app.apply_change(Change::Move(TaskMove::PrevList(count))),
+        Action::ViewTask
+            if !app.focused_list_is_empty() => {
                 app.change_state(AppState::TaskView(Box::new(state)));
-            }
-        },
-        KeyCode::Char('b') => app.change_state(AppState::BacklogPopup(Box::new(state))),
-        KeyCode::Char('B') => app.move_task_to_backlog(),
-        KeyCode::Char('a') => app.change_state(AppState::ArchivePopup(Box::new(state))),
-        KeyCode::Char('A') => app.move_task_to_archive(),
+            },
+        Action::SetCategory
+            if app.has_marked_tasks() || !app.focused_list_is_empty() => {
+                app.populate_category_input();
+                app.change_state(AppState::SetCategory(Box::new(state)));
+            },
+        Action::ToggleBacklog => app.change_state(AppState::BacklogPopup(Box::new(state))),
+        Action::MoveTaskToBacklog => app.apply_change(Change::Move(TaskMove::Backlog)),
+        Action::ToggleArchive => app.open_archive(state),
+        Action::MoveTaskToArchive => app.apply_change(Change::Move(TaskMove::Archive)),
+        Action::ToggleTimer => app.toggle_timer(),
+        Action::ToggleStatistics => app.change_state(AppState::Statistics(Box::new(state))),
+        Action::ToggleFlowCharts => app.change_state(AppState::FlowCharts(Box::new(state))),
+        Action::Help => app.open_help(),
         _ => {}
     }
 
@@ -330,26 +473,27 @@ fn handle_task_view_events(
     app: &mut App,
     prev: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char('q') => app.set_quit(true),
-        KeyCode::Char('s') => app.save_changes()?,
-        KeyCode::Char('o') => {
+    let action = match app.config.keymap.action(Mode::TaskView, key) {
+        Some(action) => action,
+        None => return Ok(())
+    };
+
+    match action {
+        Action::Quit => app.set_quit(true),
+        Action::Save => app.save_changes()?,
+        Action::OpenProjectMenu => {
             app.save_changes()?;
             app.move_up_one_directory()?;
             app.change_state(AppState::ProjectMenu);
         },
-        KeyCode::Char('j') => app.scroll_details(1),
-        KeyCode::Down => app.scroll_details(1),
-        KeyCode::Char('k') => app.scroll_details(-1),
-        KeyCode::Up => app.scroll_details(-1),
-        KeyCode::Enter => {
-            app.reset_scroll();
-            app.change_state(prev);
-        },
-        KeyCode::Esc => {
+        Action::ScrollDown => app.scroll_details(1),
+        Action::ScrollUp => app.scroll_details(-1),
+        Action::ToggleTimer => app.toggle_timer(),
+        Action::Close => {
             app.reset_scroll();
             app.change_state(prev);
         },
+        Action::Help => app.open_help(),
         _ => {}
     }
 
@@ -361,15 +505,20 @@ fn handle_backlog_popup_events(
     app: &mut App,
     prev: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char('q') => app.set_quit(true),
-        KeyCode::Char('s') => app.save_changes()?,
-        KeyCode::Char('o') => {
+    let (action, count) = match get_counted_action(Mode::BacklogPopup, key, app) {
+        Some(action) => action,
+        None => return Ok(())
+    };
+
+    match action {
+        Action::Quit => app.set_quit(true),
+        Action::Save => app.save_changes()?,
+        Action::OpenProjectMenu => {
             app.save_changes()?;
             app.move_up_one_directory()?;
             app.change_state(AppState::ProjectMenu);
         },
-        KeyCode::Char('n') => {
+        Action::NewTask => {
             app.clear_detail_inputs();
             app.reset_active_detail_input();
             app.change_state(
@@ -382,10 +531,13 @@ fn handle_backlog_popup_events(
                 )
             );
         },
-        KeyCode::Char('d') => {
-            if !app.focused_list_is_empty() {
+        Action::DeleteTask => app.apply_change(Change::Delete),
+        Action::EditTask
+            if !app.focused_list_is_empty() => {
+                app.populate_task_detail_inputs();
+                app.reset_active_detail_input();
                 app.change_state(
-                    AppState::DeleteTask(
+                    AppState::EditTask(
                         Box::new(
                             AppState::BacklogPopup(
                                 Box::new(prev)
@@ -393,14 +545,44 @@ fn handle_backlog_popup_events(
                         )
                     )
                 );
-            }
-        },
-        KeyCode::Char('e') => {
-            if !app.focused_list_is_empty() {
-                app.populate_task_detail_inputs();
-                app.reset_active_detail_input();
+            },
+        Action::FocusDown => (0..count).for_each(|_| app.list_down()),
+        Action::FocusUp => (0..count).for_each(|_| app.list_up()),
+        Action::MoveTaskDown => app.apply_change(Change::TaskDown(count)),
+        Action::MoveTaskUp => app.apply_change(Change::TaskUp(count)),
+        Action::MoveTaskToBottom => app.apply_change(Change::TaskToBottom),
+        Action::MoveTaskToTop => app.apply_change(Change::TaskToTop),
+        Action::FocusTop => app.jump_to_list_top(),
+        Action::FocusBottom => app.jump_to_list_bottom(),
+        Action::PageDown => app.page_down(),
+        Action::PageUp => app.page_up(),
+        Action::HalfPageDown => app.half_page_down(),
+        Action::HalfPageUp => app.half_page_up(),
+        Action::MoveTaskToFirstList => app.apply_change(Change::Move(TaskMove::ToList(0))),
+        Action::NextListColor => app.cycle_list_color(1),
+        Action::PrevListColor => app.cycle_list_color(-1),
+        Action::CycleCardMode => app.cycle_card_mode(),
+        Action::CycleAllCardModes => app.cycle_all_card_modes(),
+        Action::ToggleMark => app.toggle_mark(),
+        Action::MarkRange => app.mark_range(),
+        Action::ClearMarks => app.clear_marks(),
+        Action::YankTask => app.yank_tasks(),
+        Action::CutTask => app.cut_tasks(),
+        Action::PasteBelow => app.apply_change(Change::Paste(false, count)),
+        Action::PasteAbove => app.apply_change(Change::Paste(true, count)),
+        Action::RepeatChange => app.repeat_change(count),
+        Action::SendToProject => app.open_project_picker(false),
+        Action::MoveTaskToChosenList => app.open_list_picker(),
+        Action::OpenCommandLine => app.open_command_line(),
+        Action::CopyToProject => app.open_project_picker(true),
+        Action::ToggleBacklog => app.change_state(prev),
+        Action::Close => app.change_state(prev),
+        Action::ToggleArchive => app.open_archive(prev),
+        Action::SetCategory
+            if app.has_marked_tasks() || !app.focused_list_is_empty() => {
+                app.populate_category_input();
                 app.change_state(
-                    AppState::EditTask(
+                    AppState::SetCategory(
                         Box::new(
                             AppState::BacklogPopup(
                                 Box::new(prev)
@@ -408,54 +590,10 @@ fn handle_backlog_popup_events(
                         )
                     )
                 );
-            }
-        },
-        KeyCode::Char('j') => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_down(),
-                KeyModifiers::CONTROL => app.task_to_bottom(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('J') => app.task_down(),
-        KeyCode::Down => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_down(),
-                KeyModifiers::CONTROL => app.task_down(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('k') => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_up(),
-                KeyModifiers::CONTROL => app.task_to_top(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('K') => app.task_up(),
-        KeyCode::Up => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_up(),
-                KeyModifiers::CONTROL => app.task_up(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('g') => app.jump_to_list_top(),
-        KeyCode::Home => app.jump_to_list_top(),
-        KeyCode::Char('G') => app.jump_to_list_bottom(),
-        KeyCode::End => app.jump_to_list_bottom(),
-        KeyCode::Char(' ') => app.move_task_to_list(0),
-        KeyCode::Char('c') => app.cycle_list_color(1),
-        KeyCode::Char('C') => app.cycle_list_color(-1),
-        KeyCode::Char('b') => app.change_state(prev),
-        KeyCode::Esc => app.change_state(prev),
-        KeyCode::Char('a') => app.change_state(
-            AppState::ArchivePopup(
-                Box::new(prev)
-            )
-        ),
-        KeyCode::Enter => {
-            if !app.focused_list_is_empty() {
+            },
+        Action::ToggleTimer => app.toggle_timer(),
+        Action::ViewTask
+            if !app.focused_list_is_empty() => {
                 app.change_state(
                     AppState::TaskView(
                         Box::new(
@@ -465,8 +603,8 @@ fn handle_backlog_popup_events(
                         )
                     )
                 );
-            }
-        },
+            },
+        Action::Help => app.open_help(),
         _ => {}
     }
 
@@ -478,76 +616,84 @@ fn handle_archive_popup_events(
     app: &mut App,
     prev: AppState
 ) -> Result<(), io::Error> {
-    match key.code {
-        KeyCode::Char('q') => app.set_quit(true),
-        KeyCode::Char('s') => app.save_changes()?,
-        KeyCode::Char('o') => {
+    let (action, count) = match get_counted_action(Mode::ArchivePopup, key, app) {
+        Some(action) => action,
+        None => return Ok(())
+    };
+
+    match action {
+        Action::Quit => app.set_quit(true),
+        Action::Save => app.save_changes()?,
+        Action::OpenProjectMenu => {
             app.save_changes()?;
             app.move_up_one_directory()?;
             app.change_state(AppState::ProjectMenu);
         },
-        KeyCode::Char('d') => {
-            if !app.focused_list_is_empty() {
-                app.change_state(
-                    AppState::DeleteTask(
-                        Box::new(
-                            AppState::ArchivePopup(
-                                Box::new(prev)
-                            )
+        Action::DeleteTask => app.apply_change(Change::Delete),
+        Action::FocusDown => (0..count).for_each(|_| app.list_down()),
+        Action::FocusUp => (0..count).for_each(|_| app.list_up()),
+        Action::FocusTop => app.jump_to_list_top(),
+        Action::FocusBottom => app.jump_to_list_bottom(),
+        Action::PageDown => app.page_down(),
+        Action::PageUp => app.page_up(),
+        Action::HalfPageDown => app.half_page_down(),
+        Action::HalfPageUp => app.half_page_up(),
+        Action::Search => {
+            app.change_state(
+                AppState::ArchiveSearch(
+                    Box::new(
+                        AppState::ArchivePopup(
+                            Box::new(prev)
                         )
                     )
-                );
-            }
-        },
-        KeyCode::Char('j') => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_down(),
-                KeyModifiers::CONTROL => app.task_to_bottom(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('J') => app.task_down(),
-        KeyCode::Down => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_down(),
-                KeyModifiers::CONTROL => app.task_down(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('k') => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_up(),
-                KeyModifiers::CONTROL => app.task_to_top(),
-                _ => {}
-            }
-        },
-        KeyCode::Char('K') => app.task_up(),
-        KeyCode::Up => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.list_up(),
-                KeyModifiers::CONTROL => app.task_up(),
-                _ => {}
-            }
+                )
+            );
         },
-        KeyCode::Char('g') => app.jump_to_list_top(),
-        KeyCode::Home => app.jump_to_list_top(),
-        KeyCode::Char('G') => app.jump_to_list_bottom(),
-        KeyCode::End => app.jump_to_list_bottom(),
-        KeyCode::Char(' ') => {
+        Action::CycleArchiveGrouping => app.cycle_archive_grouping(),
+        Action::LoadOlderArchive => app.load_older_archive()?,
+        Action::RestoreTask => app.apply_change(Change::Move(TaskMove::Restore)),
+        Action::MoveTaskToLastList => {
             let dest_index = app.task_lists.len() - 1;
-            app.move_task_to_list(dest_index);
+            app.apply_change(Change::Move(TaskMove::ToList(dest_index)));
         },
-        KeyCode::Char('c') => app.cycle_list_color(1),
-        KeyCode::Char('C') => app.cycle_list_color(-1),
-        KeyCode::Char('a') => app.change_state(prev),
-        KeyCode::Esc => app.change_state(prev),
-        KeyCode::Char('b') => app.change_state(
+        Action::NextListColor => app.cycle_list_color(1),
+        Action::PrevListColor => app.cycle_list_color(-1),
+        Action::CycleCardMode => app.cycle_card_mode(),
+        Action::CycleAllCardModes => app.cycle_all_card_modes(),
+        Action::ToggleMark => app.toggle_mark(),
+        Action::MarkRange => app.mark_range(),
+        Action::ClearMarks => app.clear_marks(),
+        Action::YankTask => app.yank_tasks(),
+        Action::CutTask => app.cut_tasks(),
+        Action::PasteBelow => app.apply_change(Change::Paste(false, count)),
+        Action::PasteAbove => app.apply_change(Change::Paste(true, count)),
+        Action::RepeatChange => app.repeat_change(count),
+        Action::SendToProject => app.open_project_picker(false),
+        Action::MoveTaskToChosenList => app.open_list_picker(),
+        Action::OpenCommandLine => app.open_command_line(),
+        Action::CopyToProject => app.open_project_picker(true),
+        Action::ToggleArchive => app.change_state(prev),
+        Action::Close => app.change_state(prev),
+        Action::ToggleBacklog => app.change_state(
             AppState::BacklogPopup(
                 Box::new(prev)
             )
         ),
-        KeyCode::Enter => {
-            if !app.focused_list_is_empty() {
+        Action::SetCategory
+            if app.has_marked_tasks() || !app.focused_list_is_empty() => {
+                app.populate_category_input();
+                app.change_state(
+                    AppState::SetCategory(
+                        Box::new(
+                            AppState::ArchivePopup(
+                                Box::new(prev)
+                            )
+                        )
+                    )
+                );
+            },
+        Action::ViewTask
+            if !app.focused_list_is_empty() => {
                 app.change_state(
                     AppState::TaskView(
                         Box::new(
@@ -557,210 +703,329 @@ fn handle_archive_popup_events(
                         )
                     )
                 );
-            }
-        },
+            },
+        Action::Help => app.open_help(),
         _ => {}
     }
+    app.sync_archive_focus();
 
     Ok(())
 }
 
 fn handle_edit_task_events(key: KeyEvent, app: &mut App, prev: AppState) {
-    match key.code {
-        KeyCode::Char(c) => {
-            match key {
-                KeyEvent {
-                    code: KeyCode::Char('w'),
-                    modifiers: KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE
-                } => app.delete_to_prev_space(),
-                _ => app.add_to_detail_input(c)
+    match app.config.keymap.action(Mode::FormEditor, key) {
+        Some(Action::NextInput) => app.next_detail_input(),
+        Some(Action::PrevInput) => app.prev_detail_input(),
+        Some(Action::Confirm) => {
+            if app.save_details_to_task() {
+                app.change_state(prev);
             }
         },
-        KeyCode::Backspace => app.delete_from_detail_input(),
-        KeyCode::Delete => app.clear_focused_input(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_left(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_left(),
-                _ => {}
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+}
+
+fn handle_create_task_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::FormEditor, key) {
+        Some(Action::NextInput) => app.next_detail_input(),
+        Some(Action::PrevInput) => app.prev_detail_input(),
+        Some(Action::Confirm) => {
+            if app.save_details_to_task() {
+                app.change_state(prev);
             }
         },
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_right(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_right(),
-                _ => {}
-            }
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+}
+
+fn handle_delete_task_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Prompt, key) {
+        Some(Action::Confirm) => {
+            app.delete_highlighted_task();
+            app.change_state(prev);
         },
-        KeyCode::Home => app.input_start(),
-        KeyCode::End => app.input_end(),
-        KeyCode::Tab => app.next_detail_input(),
-        KeyCode::BackTab => app.prev_detail_input(),
-        KeyCode::Down => app.next_detail_input(),
-        KeyCode::Up => app.prev_detail_input(),
-        KeyCode::Enter => {
-            app.save_details_to_task();
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        _ => {}
+    }
+}
+
+fn handle_set_category_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::InputEditor, key) {
+        Some(Action::Confirm) => {
+            app.save_category();
             app.change_state(prev);
+        },
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+}
+
+fn handle_picker_action(action: Action, app: &mut App) {
+    match action {
+        Action::FocusDown => app.picker.select_next(),
+        Action::FocusUp => app.picker.select_prev(),
+        action => {
+            handle_input_action(action, app);
+            app.picker.reset_selection();
         }
-        KeyCode::Esc => app.change_state(prev),
-        _ => {}
     }
 }
 
-fn handle_create_task_events(key: KeyEvent, app: &mut App, prev: AppState) {
-    match key.code {
-        KeyCode::Char(c) => {
-            match key {
-                KeyEvent {
-                    code: KeyCode::Char('w'),
-                    modifiers: KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE
-                } => app.delete_to_prev_space(),
-                _ => app.add_to_detail_input(c)
+fn handle_picker_typed_char(key: KeyEvent, app: &mut App) {
+    handle_typed_char(key, app);
+    app.picker.reset_selection();
+}
+
+fn handle_send_to_project_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Picker, key) {
+        Some(Action::Confirm) => {
+            // A broken project shouldn't take the open one down with it
+            if let Err(err) = app.pick_project() {
+                app.message = Some(format!("Couldn't read that project: {}", err));
             }
         },
-        KeyCode::Backspace => app.delete_from_detail_input(),
-        KeyCode::Delete => app.clear_focused_input(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_left(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_left(),
-                _ => {}
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_picker_action(action, app),
+        None => handle_picker_typed_char(key, app)
+    }
+}
+
+fn handle_send_to_project_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Picker, key) {
+        Some(Action::Confirm) => {
+            if let Err(err) = app.send_tasks_to_project() {
+                app.message = Some(format!("Couldn't add to '{}': {}", app.send_project, err));
             }
+            app.change_state(prev);
         },
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_right(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_right(),
-                _ => {}
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_picker_action(action, app),
+        None => handle_picker_typed_char(key, app)
+    }
+}
+
+fn handle_move_to_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Picker, key) {
+        Some(Action::Confirm) => {
+            if let Some(i) = app.picker.get_selected_item() {
+                app.change_state(prev);
+                app.apply_change(Change::Move(TaskMove::ToList(i)));
             }
         },
-        KeyCode::Home => app.input_start(),
-        KeyCode::End => app.input_end(),
-        KeyCode::Tab => app.next_detail_input(),
-        KeyCode::BackTab => app.prev_detail_input(),
-        KeyCode::Down => app.next_detail_input(),
-        KeyCode::Up => app.prev_detail_input(),
-        KeyCode::Enter => {
-            app.save_details_to_task();
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_picker_action(action, app),
+        None => {
+            // A number typed before any filter picks that list straight away
+            match key.code {
+                KeyCode::Char(c @ '1'..='9') if app.picker.input.text.is_empty() => {
+                    let i = c as usize - '1' as usize;
+                    if i < app.task_lists.len() {
+                        app.change_state(prev);
+                        app.apply_change(Change::Move(TaskMove::ToList(i)));
+                    }
+                },
+                _ => handle_picker_typed_char(key, app)
+            }
+        }
+    }
+}
+
+fn handle_command_line_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::CommandLine, key) {
+        Some(Action::Complete) => app.complete_command(true),
+        Some(Action::CompletePrev) => app.complete_command(false),
+        Some(Action::Confirm) => {
             app.change_state(prev);
+            if let Err(err) = app.run_command() {
+                app.message = Some(err.to_string());
+            }
+        },
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => {
+            handle_input_action(action, app);
+            app.completion = None;
+        },
+        None => {
+            handle_typed_char(key, app);
+            app.completion = None;
         }
-        KeyCode::Esc => app.change_state(prev),
-        _ => {}
     }
 }
 
-fn handle_delete_task_events(key: KeyEvent, app: &mut App, prev: AppState) {
-    match key.code {
-        KeyCode::Char('y') => {
-            app.delete_highlighted_task();
+fn handle_edit_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::FormEditor, key) {
+        Some(Action::NextInput) => app.next_detail_input(),
+        Some(Action::PrevInput) => app.prev_detail_input(),
+        Some(Action::Confirm) => {
+            app.save_details_to_list();
             app.change_state(prev);
         },
-        KeyCode::Char('n') => app.change_state(prev),
-        KeyCode::Enter => {
-            app.delete_highlighted_task();
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+}
+
+fn handle_create_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::FormEditor, key) {
+        Some(Action::NextInput) => app.next_detail_input(),
+        Some(Action::PrevInput) => app.prev_detail_input(),
+        Some(Action::Confirm) => {
+            app.save_details_to_list();
+            app.change_state(prev);
+        },
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+}
+
+fn handle_delete_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Prompt, key) {
+        Some(Action::Confirm) => {
+            app.delete_focused_list();
             app.change_state(prev);
         },
-        KeyCode::Esc => app.change_state(prev),
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
         _ => {}
     }
 }
 
-fn handle_edit_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
-    match key.code {
-        KeyCode::Char(c) => {
-            match key {
-                KeyEvent {
-                    code: KeyCode::Char('w'),
-                    modifiers: KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE
-                } => app.delete_to_prev_space(),
-                _ => app.add_to_detail_input(c)
-            }
+fn handle_confirm_move_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Prompt, key) {
+        Some(Action::Confirm) => {
+            app.change_state(prev);
+            app.confirm_task_move();
         },
-        KeyCode::Backspace => app.delete_from_detail_input(),
-        KeyCode::Delete => app.clear_focused_input(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_left(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_left(),
-                _ => {}
-            }
+        Some(Action::Cancel) => {
+            app.cancel_task_move();
+            app.change_state(prev);
         },
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_right(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_right(),
-                _ => {}
-            }
+        Some(Action::Help) => app.open_help(),
+        _ => {}
+    }
+}
+
+fn handle_statistics_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::Statistics, key) {
+        Some(Action::ToggleStatistics | Action::Close) => app.change_state(prev),
+        Some(Action::ToggleFlowCharts) => {
+            app.change_state(
+                AppState::FlowCharts(
+                    Box::new(
+                        AppState::Statistics(
+                            Box::new(prev)
+                        )
+                    )
+                )
+            );
         },
-        KeyCode::Home => app.input_start(),
-        KeyCode::End => app.input_end(),
-        KeyCode::Enter => {
-            app.save_details_to_list();
-            app.change_state(prev);
-        }
-        KeyCode::Esc => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
         _ => {}
     }
 }
 
-fn handle_create_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
-    match key.code {
-        KeyCode::Char(c) => {
-            match key {
-                KeyEvent {
-                    code: KeyCode::Char('w'),
-                    modifiers: KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    state: KeyEventState::NONE
-                } => app.delete_to_prev_space(),
-                _ => app.add_to_detail_input(c)
-            }
+fn handle_flow_charts_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::FlowCharts, key) {
+        Some(Action::ToggleFlowCharts | Action::Close) => app.change_state(prev),
+        Some(Action::SetBurndownTarget) => {
+            app.populate_burndown_target_input();
+            app.change_state(
+                AppState::SetBurndownTarget(
+                    Box::new(
+                        AppState::FlowCharts(
+                            Box::new(prev)
+                        )
+                    )
+                )
+            );
         },
-        KeyCode::Backspace => app.delete_from_detail_input(),
-        KeyCode::Delete => app.clear_focused_input(),
-        KeyCode::Left => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_left(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_left(),
-                _ => {}
-            }
+        Some(Action::Help) => app.open_help(),
+        _ => {}
+    }
+}
+
+fn handle_set_burndown_target_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::InputEditor, key) {
+        Some(Action::Confirm) => {
+            app.save_burndown_target();
+            app.change_state(prev);
         },
-        KeyCode::Right => {
-            match key.modifiers {
-                KeyModifiers::NONE => app.input_right(),
-                KeyModifiers::CONTROL => app.input_jump_to_space_right(),
-                _ => {}
-            }
+        Some(Action::Cancel) => app.change_state(prev),
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+}
+
+fn handle_help_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    let action = match app.config.keymap.action(Mode::Help, key) {
+        Some(action) => action,
+        None => return
+    };
+
+    match action {
+        Action::ScrollDown => app.scroll_help(1),
+        Action::ScrollUp => app.scroll_help(-1),
+        Action::FocusTop => app.scroll_help_to_top(),
+        Action::FocusBottom => app.scroll_help_to_bottom(),
+        Action::Search => {
+            app.change_state(
+                AppState::HelpSearch(
+                    Box::new(
+                        AppState::Help(
+                            Box::new(prev)
+                        )
+                    )
+                )
+            );
         },
-        KeyCode::Home => app.input_start(),
-        KeyCode::End => app.input_end(),
-        KeyCode::Enter => {
-            app.save_details_to_list();
-            app.change_state(prev);
-        }
-        KeyCode::Esc => app.change_state(prev),
+        Action::Close => app.change_state(prev),
         _ => {}
     }
 }
 
-fn handle_delete_list_events(key: KeyEvent, app: &mut App, prev: AppState) {
-    match key.code {
-        KeyCode::Char('y') => {
-            app.delete_focused_list();
+fn handle_archive_search_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::InputEditor, key) {
+        Some(Action::Confirm) => app.change_state(prev),
+        Some(Action::Cancel) => {
+            app.clear_focused_input();
             app.change_state(prev);
         },
-        KeyCode::Char('n') => app.change_state(prev),
-        KeyCode::Enter => {
-            app.delete_focused_list();
+        Some(Action::Help) => app.open_help(),
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
+    }
+
+    app.sync_archive_focus();
+}
+
+fn handle_help_search_events(key: KeyEvent, app: &mut App, prev: AppState) {
+    match app.config.keymap.action(Mode::InputEditor, key) {
+        Some(Action::Confirm) => app.change_state(prev),
+        Some(Action::Cancel) => {
+            app.clear_focused_input();
             app.change_state(prev);
         },
-        KeyCode::Esc => app.change_state(prev),
-        _ => {}
+        Some(action) => handle_input_action(action, app),
+        None => handle_typed_char(key, app)
     }
+
+    app.scroll_help_to_top();
 }
@@ -44,7 +44,7 @@ impl Input {
     pub fn num_trailing_spaces(&self) -> usize {
         let mut text = self.text.clone();
         let mut counter = 0;
-        while text.len() > 0 && &text[text.len() - 1..] == " " {
+        while !text.is_empty() && &text[text.len() - 1..] == " " {
             counter += 1;
             text.pop();
         }
@@ -0,0 +1,719 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::{collections::HashMap, fmt};
+
+use crate::app::*;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    ProjectMenu,
+    Tracker,
+    TaskView,
+    BacklogPopup,
+    ArchivePopup,
+    InputEditor,
+    FormEditor,
+    Prompt,
+    Picker,
+    CommandLine,
+    Statistics,
+    FlowCharts,
+    Help,
+}
+
+impl From<&AppState> for Mode {
+    fn from(state: &AppState) -> Self {
+        match state {
+            AppState::ProjectMenu => Mode::ProjectMenu,
+            AppState::EditProject(_) => Mode::InputEditor,
+            AppState::CreateProject(_) => Mode::InputEditor,
+            AppState::DeleteProject(_) => Mode::Prompt,
+            AppState::Tracker => Mode::Tracker,
+            AppState::TaskView(_) => Mode::TaskView,
+            AppState::BacklogPopup(_) => Mode::BacklogPopup,
+            AppState::ArchivePopup(_) => Mode::ArchivePopup,
+            AppState::ArchiveSearch(_) => Mode::InputEditor,
+            AppState::EditTask(_) => Mode::FormEditor,
+            AppState::CreateTask(_) => Mode::FormEditor,
+            AppState::DeleteTask(_) => Mode::Prompt,
+            AppState::SetCategory(_) => Mode::InputEditor,
+            AppState::SendToProject(_) => Mode::Picker,
+            AppState::SendToProjectList(_) => Mode::Picker,
+            AppState::MoveToList(_) => Mode::Picker,
+            AppState::CommandLine(_) => Mode::CommandLine,
+            AppState::EditList(_) => Mode::FormEditor,
+            AppState::CreateList(_) => Mode::FormEditor,
+            AppState::DeleteList(_) => Mode::Prompt,
+            AppState::ConfirmMove(_) => Mode::Prompt,
+            AppState::Statistics(_) => Mode::Statistics,
+            AppState::FlowCharts(_) => Mode::FlowCharts,
+            AppState::SetBurndownTarget(_) => Mode::InputEditor,
+            AppState::Help(_) => Mode::Help,
+            AppState::HelpSearch(_) => Mode::InputEditor,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Save,
+    OpenProjectMenu,
+    SelectProject,
+    NewProject,
+    EditProject,
+    DeleteProject,
+    FocusDown,
+    FocusUp,
+    FocusTop,
+    FocusBottom,
+    FocusNextList,
+    FocusPrevList,
+    FocusNextLane,
+    FocusPrevLane,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    MoveTaskDown,
+    MoveTaskUp,
+    MoveTaskToTop,
+    MoveTaskToBottom,
+    MoveListLeft,
+    MoveListRight,
+    ToggleCollapse,
+    NewTask,
+    EditTask,
+    DeleteTask,
+    ViewTask,
+    SetCategory,
+    ToggleMark,
+    MarkRange,
+    ClearMarks,
+    YankTask,
+    CutTask,
+    PasteBelow,
+    PasteAbove,
+    SendToProject,
+    CopyToProject,
+    NewList,
+    EditList,
+    DeleteList,
+    NextListColor,
+    PrevListColor,
+    CycleCardMode,
+    CycleAllCardModes,
+    ToggleSwimlanes,
+    MoveTaskToNextList,
+    MoveTaskToPrevList,
+    MoveTaskToFirstList,
+    MoveTaskToLastList,
+    MoveTaskToChosenList,
+    RepeatChange,
+    OpenCommandLine,
+    MoveTaskToBacklog,
+    MoveTaskToArchive,
+    RestoreTask,
+    CycleArchiveGrouping,
+    LoadOlderArchive,
+    ToggleBacklog,
+    ToggleArchive,
+    ToggleStatistics,
+    ToggleFlowCharts,
+    ToggleTimer,
+    SetBurndownTarget,
+    ScrollDown,
+    ScrollUp,
+    Close,
+    Confirm,
+    Cancel,
+    CursorLeft,
+    CursorRight,
+    CursorWordLeft,
+    CursorWordRight,
+    CursorStart,
+    CursorEnd,
+    DeleteChar,
+    DeleteWord,
+    ClearInput,
+    NextInput,
+    Complete,
+    CompletePrev,
+    PrevInput,
+    Help,
+    Search,
+}
+
+impl Action {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit kadai",
+            Action::Save => "save without exiting",
+            Action::OpenProjectMenu => "save and return to project select menu",
+            Action::SelectProject => "select project to open",
+            Action::NewProject => "create new project",
+            Action::EditProject => "edit project",
+            Action::DeleteProject => "delete focused project",
+            Action::FocusDown => "move focus down",
+            Action::FocusUp => "move focus up",
+            Action::FocusTop => "jump to top of list",
+            Action::FocusBottom => "jump to bottom of list",
+            Action::FocusNextList => "focus next list",
+            Action::FocusPrevList => "focus previous list",
+            Action::FocusNextLane => "focus next swimlane",
+            Action::FocusPrevLane => "focus previous swimlane",
+            Action::PageDown => "move focus down a page",
+            Action::PageUp => "move focus up a page",
+            Action::HalfPageDown => "move focus down half a page",
+            Action::HalfPageUp => "move focus up half a page",
+            Action::MoveTaskDown => "move task down",
+            Action::MoveTaskUp => "move task up",
+            Action::MoveTaskToTop => "move task to top of list",
+            Action::MoveTaskToBottom => "move task to bottom of list",
+            Action::MoveListLeft => "move list left",
+            Action::MoveListRight => "move list right",
+            Action::ToggleCollapse => "collapse/expand focused list",
+            Action::NewTask => "add new task",
+            Action::EditTask => "edit task",
+            Action::DeleteTask => "delete focused task",
+            Action::ViewTask => "view focused task details",
+            Action::SetCategory => "set category of focused or marked tasks",
+            Action::ToggleMark => "mark/unmark focused task",
+            Action::MarkRange => "mark tasks from last marked to focused",
+            Action::ClearMarks => "unmark all tasks",
+            Action::YankTask => "yank focused or marked tasks",
+            Action::CutTask => "cut focused or marked tasks",
+            Action::PasteBelow => "paste tasks below focused task",
+            Action::PasteAbove => "paste tasks above focused task",
+            Action::SendToProject => "send focused or marked tasks to another project",
+            Action::CopyToProject => "copy focused or marked tasks to another project",
+            Action::NewList => "add new list",
+            Action::EditList => "edit list",
+            Action::DeleteList => "delete focused list",
+            Action::NextListColor => "cycle list color forward",
+            Action::PrevListColor => "cycle list color backward",
+            Action::CycleCardMode => "cycle card display mode of list",
+            Action::CycleAllCardModes => "cycle card display mode of all lists",
+            Action::ToggleSwimlanes => "toggle swimlanes by category",
+            Action::MoveTaskToNextList => "move focused task to next list",
+            Action::MoveTaskToPrevList => "move focused task to previous list",
+            Action::MoveTaskToFirstList => "move focused task to first list",
+            Action::MoveTaskToLastList => "move focused task to last list",
+            Action::RepeatChange => "repeat last change to tasks",
+            Action::OpenCommandLine => "open command line",
+            Action::MoveTaskToChosenList => "move focused or marked tasks to a list by number or name",
+            Action::MoveTaskToBacklog => "move focused task to backlog",
+            Action::MoveTaskToArchive => "move focused task to archive",
+            Action::RestoreTask => "restore focused task to the list it was archived from",
+            Action::CycleArchiveGrouping => "group archive by week or month",
+            Action::LoadOlderArchive => "load tasks archived in the next older year",
+            Action::ToggleBacklog => "open/close backlog popup",
+            Action::ToggleArchive => "open/close archive browser",
+            Action::ToggleStatistics => "open/close project statistics",
+            Action::ToggleFlowCharts => "open/close cumulative flow and burndown charts",
+            Action::SetBurndownTarget => "set burndown target date",
+            Action::ToggleTimer => "start/stop timer on focused task",
+            Action::ScrollDown => "scroll down",
+            Action::ScrollUp => "scroll up",
+            Action::Close => "close",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::CursorLeft => "move cursor left",
+            Action::CursorRight => "move cursor right",
+            Action::CursorWordLeft => "move cursor left to nearest space",
+            Action::CursorWordRight => "move cursor right to nearest space",
+            Action::CursorStart => "move cursor to beginning of input",
+            Action::CursorEnd => "move cursor to end of input",
+            Action::DeleteChar => "delete character from input",
+            Action::DeleteWord => "delete input to previous space",
+            Action::ClearInput => "clear input",
+            Action::NextInput => "focus next input",
+            Action::Complete => "complete command or name",
+            Action::CompletePrev => "cycle completions backwards",
+            Action::PrevInput => "focus previous input",
+            Action::Help => "show keybindings",
+            Action::Search => "search",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // The case of a character already tells us whether shift was held,
+        // so we fold it away to make "J" and "Shift+j" the same key
+        match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => Self {
+                code: KeyCode::BackTab,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            KeyCode::BackTab => Self {
+                code,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            _ => Self { code, modifiers },
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let (prefix, name) = if text.len() > 1 && text.ends_with("++") {
+            (&text[..text.len() - 2], "+")
+        } else {
+            match text.rsplit_once('+') {
+                Some((prefix, name)) if !name.is_empty() => (prefix, name),
+                _ => ("", text),
+            }
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        if !prefix.is_empty() {
+            for modifier in prefix.split('+') {
+                modifiers |= match modifier.to_lowercase().as_str() {
+                    "ctrl" | "control" => KeyModifiers::CONTROL,
+                    "alt" => KeyModifiers::ALT,
+                    "shift" => KeyModifiers::SHIFT,
+                    _ => return None
+                };
+            }
+        }
+
+        let mut chars = name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => {
+                match name.to_lowercase().as_str() {
+                    "space" => KeyCode::Char(' '),
+                    "enter" | "return" => KeyCode::Enter,
+                    "esc" | "escape" => KeyCode::Esc,
+                    "backspace" => KeyCode::Backspace,
+                    "delete" | "del" => KeyCode::Delete,
+                    "insert" => KeyCode::Insert,
+                    "tab" => KeyCode::Tab,
+                    "backtab" => KeyCode::BackTab,
+                    "up" => KeyCode::Up,
+                    "down" => KeyCode::Down,
+                    "left" => KeyCode::Left,
+                    "right" => KeyCode::Right,
+                    "home" => KeyCode::Home,
+                    "end" => KeyCode::End,
+                    "pageup" => KeyCode::PageUp,
+                    "pagedown" => KeyCode::PageDown,
+                    other => {
+                        let number = other.strip_prefix('f')?.parse().ok()?;
+                        KeyCode::F(number)
+                    }
+                }
+            }
+        };
+
+        Some(Self::new(code, modifiers))
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::BackTab => write!(f, "Shift+Tab"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+pub struct Binding {
+    pub key: Key,
+    pub action: Action,
+}
+
+pub struct Keymap {
+    bindings: HashMap<Mode, Vec<Binding>>,
+}
+
+impl Keymap {
+    pub fn action(&self, mode: Mode, event: KeyEvent) -> Option<Action> {
+        let key = Key::from(event);
+
+        self.bindings(mode)
+            .iter()
+            .find(|binding| binding.key == key)
+            .map(|binding| binding.action)
+    }
+
+    pub fn bindings(&self, mode: Mode) -> &[Binding] {
+        match self.bindings.get(&mode) {
+            Some(bindings) => bindings,
+            None => &[]
+        }
+    }
+
+    // The first key bound to the action, for hints shown on screen
+    pub fn key_for(&self, mode: Mode, action: Action) -> Option<Key> {
+        self.bindings(mode)
+            .iter()
+            .find(|binding| binding.action == action)
+            .map(|binding| binding.key)
+    }
+
+    pub fn bind(&mut self, mode: Mode, key: Key, action: Option<Action>) {
+        let bindings = self.bindings.entry(mode).or_default();
+
+        match (bindings.iter().position(|b| b.key == key), action) {
+            (Some(i), Some(action)) => bindings[i].action = action,
+            (Some(i), None) => {
+                bindings.remove(i);
+            },
+            (None, Some(action)) => bindings.push(Binding { key, action }),
+            (None, None) => {}
+        }
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let modes = [
+            Mode::ProjectMenu,
+            Mode::Tracker,
+            Mode::TaskView,
+            Mode::BacklogPopup,
+            Mode::ArchivePopup,
+            Mode::InputEditor,
+            Mode::FormEditor,
+            Mode::Prompt,
+            Mode::Picker,
+            Mode::CommandLine,
+            Mode::Statistics,
+            Mode::FlowCharts,
+            Mode::Help,
+        ];
+
+        let mut keymap = Self { bindings: HashMap::new() };
+        for mode in modes {
+            for (key, action) in default_bindings(mode) {
+                let key = Key::parse(key).expect("Invalid default key binding");
+                keymap.bind(mode, key, Some(action));
+            }
+        }
+
+        keymap
+    }
+}
+
+fn default_bindings(mode: Mode) -> Vec<(&'static str, Action)> {
+    use Action::*;
+
+    match mode {
+        Mode::ProjectMenu => vec![
+            ("q", Quit),
+            ("Esc", Quit),
+            ("n", NewProject),
+            ("e", EditProject),
+            ("d", DeleteProject),
+            ("j", FocusDown),
+            ("Down", FocusDown),
+            ("k", FocusUp),
+            ("Up", FocusUp),
+            ("g", FocusTop),
+            ("Home", FocusTop),
+            ("G", FocusBottom),
+            ("End", FocusBottom),
+            ("Space", SelectProject),
+            ("Enter", SelectProject),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::Tracker => vec![
+            ("q", Quit),
+            ("Esc", Quit),
+            ("s", Save),
+            ("o", OpenProjectMenu),
+            ("n", NewTask),
+            ("N", NewList),
+            ("e", EditTask),
+            ("E", EditList),
+            ("d", DeleteTask),
+            ("D", DeleteList),
+            ("j", FocusDown),
+            ("Down", FocusDown),
+            ("k", FocusUp),
+            ("Up", FocusUp),
+            ("J", MoveTaskDown),
+            ("Ctrl+Down", MoveTaskDown),
+            ("K", MoveTaskUp),
+            ("Ctrl+Up", MoveTaskUp),
+            ("Ctrl+j", MoveTaskToBottom),
+            ("Ctrl+k", MoveTaskToTop),
+            ("h", FocusPrevList),
+            ("Left", FocusPrevList),
+            ("l", FocusNextList),
+            ("Right", FocusNextList),
+            ("Tab", FocusNextLane),
+            ("Shift+Tab", FocusPrevLane),
+            ("H", MoveListLeft),
+            ("Ctrl+Left", MoveListLeft),
+            ("L", MoveListRight),
+            ("Ctrl+Right", MoveListRight),
+            ("f", ToggleCollapse),
+            ("g", FocusTop),
+            ("Home", FocusTop),
+            ("G", FocusBottom),
+            ("End", FocusBottom),
+            ("PageDown", PageDown),
+            ("PageUp", PageUp),
+            ("Ctrl+d", HalfPageDown),
+            ("Ctrl+u", HalfPageUp),
+            ("c", NextListColor),
+            ("C", PrevListColor),
+            ("z", CycleCardMode),
+            ("Z", CycleAllCardModes),
+            ("t", SetCategory),
+            ("T", ToggleTimer),
+            ("v", ToggleMark),
+            ("V", MarkRange),
+            ("X", ClearMarks),
+            ("y", YankTask),
+            ("x", CutTask),
+            ("p", PasteBelow),
+            ("P", PasteAbove),
+            ("S", SendToProject),
+            ("m", MoveTaskToChosenList),
+            (".", RepeatChange),
+            (":", OpenCommandLine),
+            ("Y", CopyToProject),
+            ("w", ToggleSwimlanes),
+            ("Space", MoveTaskToNextList),
+            ("Backspace", MoveTaskToPrevList),
+            ("Enter", ViewTask),
+            ("b", ToggleBacklog),
+            ("B", MoveTaskToBacklog),
+            ("a", ToggleArchive),
+            ("A", MoveTaskToArchive),
+            ("I", ToggleStatistics),
+            ("F", ToggleFlowCharts),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::TaskView => vec![
+            ("q", Quit),
+            ("s", Save),
+            ("o", OpenProjectMenu),
+            ("j", ScrollDown),
+            ("Down", ScrollDown),
+            ("k", ScrollUp),
+            ("Up", ScrollUp),
+            ("T", ToggleTimer),
+            ("Enter", Close),
+            ("Esc", Close),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::BacklogPopup => vec![
+            ("q", Quit),
+            ("s", Save),
+            ("o", OpenProjectMenu),
+            ("n", NewTask),
+            ("e", EditTask),
+            ("d", DeleteTask),
+            ("j", FocusDown),
+            ("Down", FocusDown),
+            ("k", FocusUp),
+            ("Up", FocusUp),
+            ("J", MoveTaskDown),
+            ("Ctrl+Down", MoveTaskDown),
+            ("K", MoveTaskUp),
+            ("Ctrl+Up", MoveTaskUp),
+            ("Ctrl+j", MoveTaskToBottom),
+            ("Ctrl+k", MoveTaskToTop),
+            ("g", FocusTop),
+            ("Home", FocusTop),
+            ("G", FocusBottom),
+            ("End", FocusBottom),
+            ("PageDown", PageDown),
+            ("PageUp", PageUp),
+            ("Ctrl+d", HalfPageDown),
+            ("Ctrl+u", HalfPageUp),
+            ("c", NextListColor),
+            ("C", PrevListColor),
+            ("z", CycleCardMode),
+            ("Z", CycleAllCardModes),
+            ("t", SetCategory),
+            ("v", ToggleMark),
+            ("V", MarkRange),
+            ("X", ClearMarks),
+            ("y", YankTask),
+            ("x", CutTask),
+            ("p", PasteBelow),
+            ("P", PasteAbove),
+            ("S", SendToProject),
+            ("m", MoveTaskToChosenList),
+            (".", RepeatChange),
+            (":", OpenCommandLine),
+            ("Y", CopyToProject),
+            ("T", ToggleTimer),
+            ("Space", MoveTaskToFirstList),
+            ("Enter", ViewTask),
+            ("b", ToggleBacklog),
+            ("Esc", Close),
+            ("a", ToggleArchive),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::ArchivePopup => vec![
+            ("q", Quit),
+            ("s", Save),
+            ("o", OpenProjectMenu),
+            ("d", DeleteTask),
+            ("j", FocusDown),
+            ("Down", FocusDown),
+            ("k", FocusUp),
+            ("Up", FocusUp),
+            ("g", FocusTop),
+            ("Home", FocusTop),
+            ("G", FocusBottom),
+            ("End", FocusBottom),
+            ("PageDown", PageDown),
+            ("PageUp", PageUp),
+            ("Ctrl+d", HalfPageDown),
+            ("Ctrl+u", HalfPageUp),
+            ("/", Search),
+            ("w", CycleArchiveGrouping),
+            ("O", LoadOlderArchive),
+            ("c", NextListColor),
+            ("C", PrevListColor),
+            ("z", CycleCardMode),
+            ("Z", CycleAllCardModes),
+            ("t", SetCategory),
+            ("v", ToggleMark),
+            ("V", MarkRange),
+            ("X", ClearMarks),
+            ("y", YankTask),
+            ("x", CutTask),
+            ("p", PasteBelow),
+            ("P", PasteAbove),
+            ("S", SendToProject),
+            ("m", MoveTaskToChosenList),
+            (".", RepeatChange),
+            (":", OpenCommandLine),
+            ("Y", CopyToProject),
+            ("Space", RestoreTask),
+            ("Enter", ViewTask),
+            ("a", ToggleArchive),
+            ("Esc", Close),
+            ("b", ToggleBacklog),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::InputEditor => vec![
+            ("Ctrl+w", DeleteWord),
+            ("Backspace", DeleteChar),
+            ("Delete", ClearInput),
+            ("Left", CursorLeft),
+            ("Right", CursorRight),
+            ("Ctrl+Left", CursorWordLeft),
+            ("Ctrl+Right", CursorWordRight),
+            ("Home", CursorStart),
+            ("End", CursorEnd),
+            ("Enter", Confirm),
+            ("Esc", Cancel),
+            ("F1", Help),
+        ],
+        Mode::FormEditor => {
+            let mut bindings = default_bindings(Mode::InputEditor);
+            bindings.extend([
+                ("Tab", NextInput),
+                ("Down", NextInput),
+                ("Shift+Tab", PrevInput),
+                ("Up", PrevInput),
+            ]);
+            bindings
+        },
+        Mode::Picker => {
+            let mut bindings = default_bindings(Mode::InputEditor);
+            bindings.extend([
+                ("Tab", FocusDown),
+                ("Down", FocusDown),
+                ("Shift+Tab", FocusUp),
+                ("Up", FocusUp),
+            ]);
+            bindings
+        },
+        Mode::CommandLine => {
+            let mut bindings = default_bindings(Mode::InputEditor);
+            bindings.extend([
+                ("Tab", Complete),
+                ("Shift+Tab", CompletePrev),
+            ]);
+            bindings
+        },
+        Mode::Prompt => vec![
+            ("y", Confirm),
+            ("Enter", Confirm),
+            ("n", Cancel),
+            ("Esc", Cancel),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::Statistics => vec![
+            ("I", ToggleStatistics),
+            ("F", ToggleFlowCharts),
+            ("q", Close),
+            ("Esc", Close),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::FlowCharts => vec![
+            ("F", ToggleFlowCharts),
+            ("t", SetBurndownTarget),
+            ("q", Close),
+            ("Esc", Close),
+            ("?", Help),
+            ("F1", Help),
+        ],
+        Mode::Help => vec![
+            ("j", ScrollDown),
+            ("Down", ScrollDown),
+            ("k", ScrollUp),
+            ("Up", ScrollUp),
+            ("g", FocusTop),
+            ("Home", FocusTop),
+            ("G", FocusBottom),
+            ("End", FocusBottom),
+            ("/", Search),
+            ("Esc", Close),
+            ("?", Close),
+            ("F1", Close),
+            ("q", Close),
+        ],
+    }
+}
@@ -1,20 +1,539 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta, Weekday};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::{cmp::{self, Ordering}, collections::BTreeMap, env, fmt, fs};
 use tui::widgets::ListState;
 
+use crate::inputs::*;
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct Task {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
     pub summary: String,
     pub description: Option<String>,
     pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_in: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Local>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub time_sessions: Vec<TimeSession>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<HistoryEntry>,
+    #[serde(skip)]
+    pub archive_year: Option<i32>,
+    #[serde(skip)]
+    pub marked: bool,
+}
+
+impl Task {
+    pub fn new(summary: String) -> Self {
+        Self {
+            id: None,
+            summary,
+            description: None,
+            category: None,
+            created: Some(Local::now()),
+            due: None,
+            priority: None,
+            estimate: None,
+            moved_in: None,
+            archived_from: None,
+            archived_at: None,
+            time_sessions: Vec::new(),
+            history: Vec::new(),
+            archive_year: None,
+            marked: false,
+        }
+    }
+
+    pub fn is_overdue(&self) -> bool {
+        self.due.is_some_and(|due| due < Local::now().date_naive())
+    }
+
+    pub fn contains_text(&self, text: &str) -> bool {
+        // Expects the text to be lowercase already
+        [
+            Some(&self.summary),
+            self.description.as_ref(),
+            self.category.as_ref(),
+            self.archived_from.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(text))
+    }
+
+    pub fn archive_from(&mut self, list_name: &str) {
+        self.stop_timer();
+        self.archived_from = Some(list_name.to_string());
+        self.archived_at = Some(Local::now());
+        self.archive_year = None;
+        self.record(TaskEvent::Archived { from: list_name.to_string() });
+    }
+
+    pub fn restore_to(&mut self, list_name: &str) {
+        self.unarchive();
+        self.record(TaskEvent::Restored { to: list_name.to_string() });
+    }
+
+    pub fn unarchive(&mut self) {
+        self.archived_from = None;
+        self.archived_at = None;
+        self.archive_year = None;
+    }
+
+    pub fn is_timing(&self) -> bool {
+        self.time_sessions.last().is_some_and(|session| session.end.is_none())
+    }
+
+    pub fn start_timer(&mut self) {
+        if !self.is_timing() {
+            self.time_sessions.push(TimeSession { start: Local::now(), end: None });
+        }
+    }
+
+    pub fn stop_timer(&mut self) {
+        if let Some(session) = self.time_sessions.last_mut() {
+            session.end.get_or_insert_with(Local::now);
+        }
+    }
+
+    pub fn time_spent(&self) -> TimeDelta {
+        self.time_sessions.iter().map(TimeSession::length).sum()
+    }
+
+    pub fn time_by_day(&self) -> BTreeMap<NaiveDate, TimeDelta> {
+        // Sessions running past midnight count toward both days
+        let mut days: BTreeMap<NaiveDate, TimeDelta> = BTreeMap::new();
+        for session in &self.time_sessions {
+            let mut start = session.start.naive_local();
+            let end = session.end.unwrap_or_else(Local::now).naive_local();
+            while start < end {
+                let midnight = start.date().and_time(NaiveTime::MIN) + TimeDelta::days(1);
+                let until = cmp::min(end, midnight);
+                *days.entry(start.date()).or_default() += until - start;
+                start = until;
+            }
+        }
+
+        days
+    }
+
+    pub fn record(&mut self, event: TaskEvent) {
+        self.history.push(
+            HistoryEntry {
+                at: Local::now(),
+                by: env::var("USER").or_else(|_| env::var("USERNAME")).ok(),
+                event,
+            }
+        );
+    }
+
+    pub fn edit<F: FnOnce(&mut Task)>(&mut self, change: F) {
+        // Applies the change, then records every field it touched
+        let before = self.clone();
+        change(self);
+
+        let fields = [
+            ("summary", Some(before.summary), Some(self.summary.clone())),
+            ("description", before.description, self.description.clone()),
+            ("category", before.category, self.category.clone()),
+            ("due", before.due.map(|d| d.to_string()), self.due.map(|d| d.to_string())),
+            (
+                "priority",
+                before.priority.map(|p| p.to_string()),
+                self.priority.map(|p| p.to_string())
+            ),
+            (
+                "estimate",
+                before.estimate.map(|e| e.to_string()),
+                self.estimate.map(|e| e.to_string())
+            ),
+        ];
+        for (field, old, new) in fields {
+            if old != new {
+                self.record(TaskEvent::Edited { field: field.to_string(), old, new });
+            }
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct TimeSession {
+    pub start: DateTime<Local>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<DateTime<Local>>,
+}
+
+impl TimeSession {
+    pub fn length(&self) -> TimeDelta {
+        // A running session counts up to now
+        self.end.unwrap_or_else(Local::now) - self.start
+    }
+}
+
+pub fn format_time_spent(time: TimeDelta) -> String {
+    match (time.num_hours(), time.num_minutes() % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, minutes) => format!("{}h {:02}m", hours, minutes),
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    pub at: DateTime<Local>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+    #[serde(flatten)]
+    pub event: TaskEvent,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TaskEvent {
+    Created { list: String },
+    Edited { field: String, old: Option<String>, new: Option<String> },
+    Moved { from: String, to: String },
+    Backlogged { from: String },
+    Archived { from: String },
+    Restored { to: String },
+}
+
+impl fmt::Display for TaskEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TaskEvent::Created { list } => write!(f, "created in '{}'", list),
+            // Descriptions are too long to repeat in full
+            TaskEvent::Edited { field, .. } if field == "description" => {
+                write!(f, "edited description")
+            },
+            TaskEvent::Edited { field, old: None, new: Some(new) } => {
+                write!(f, "set {} to '{}'", field, new)
+            },
+            TaskEvent::Edited { field, old: Some(old), new: None } => {
+                write!(f, "cleared {} (was '{}')", field, old)
+            },
+            TaskEvent::Edited { field, old, new } => write!(
+                f,
+                "changed {} from '{}' to '{}'",
+                field,
+                old.as_deref().unwrap_or_default(),
+                new.as_deref().unwrap_or_default()
+            ),
+            TaskEvent::Moved { from, to } => write!(f, "moved from '{}' to '{}'", from, to),
+            TaskEvent::Backlogged { from } => write!(f, "moved from '{}' to the backlog", from),
+            TaskEvent::Archived { from } => write!(f, "archived from '{}'", from),
+            TaskEvent::Restored { to } => write!(f, "restored to '{}'", to),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+impl Priority {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.to_lowercase().as_str() {
+            "high" | "h" | "3" => Some(Priority::High),
+            "medium" | "med" | "m" | "2" => Some(Priority::Medium),
+            "low" | "l" | "1" => Some(Priority::Low),
+            _ => None
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Priority::High => write!(f, "High"),
+            Priority::Medium => write!(f, "Medium"),
+            Priority::Low => write!(f, "Low"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EstimateUnit {
+    #[default]
+    Points,
+    Hours,
+}
+
+impl EstimateUnit {
+    pub fn name(self) -> &'static str {
+        match self {
+            EstimateUnit::Points => "Points",
+            EstimateUnit::Hours => "Hours",
+        }
+    }
+
+    pub fn format(self, estimate: f64) -> String {
+        // Sums of fractions are rounded so they don't trail off
+        let estimate = ((estimate * 10.0).round() / 10.0).to_string();
+        match self {
+            EstimateUnit::Points if estimate == "1" => "1 pt".to_string(),
+            EstimateUnit::Points => format!("{} pts", estimate),
+            EstimateUnit::Hours => format!("{}h", estimate),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TaskFilter {
+    pub category: Option<String>,
+    pub text: Option<String>,
+}
+
+impl TaskFilter {
+    pub fn parse(args: &str) -> Option<Self> {
+        // Takes terms like "cat=bug login", where anything that isn't
+        // "cat=" or "category=" is text to look for in the task
+        let mut category = None;
+        let mut words = Vec::new();
+
+        for term in args.split_whitespace() {
+            match term.split_once('=') {
+                Some(("cat" | "category", value)) => category = Some(value.to_lowercase()),
+                _ => words.push(term)
+            }
+        }
+
+        let text = if words.is_empty() {
+            None
+        } else {
+            Some(words.join(" ").to_lowercase())
+        };
+
+        if category.is_none() && text.is_none() {
+            return None;
+        }
+
+        Some(Self { category, text })
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        if let Some(category) = &self.category {
+            match &task.category {
+                Some(c) if c.to_lowercase() == *category => {},
+                _ => return false
+            }
+        }
+
+        if let Some(text) = &self.text {
+            let in_summary = task.summary.to_lowercase().contains(text);
+            let in_description = task.description
+                .as_ref()
+                .is_some_and(|d| d.to_lowercase().contains(text));
+
+            if !in_summary && !in_description {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl fmt::Display for TaskFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut terms = Vec::new();
+        if let Some(category) = &self.category {
+            terms.push(format!("cat={}", category));
+        }
+        if let Some(text) = &self.text {
+            terms.push(text.clone());
+        }
+
+        write!(f, "{}", terms.join(" "))
+    }
+}
+
+#[derive(Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortKey {
+    Summary,
+    Category,
+    Created,
+    Due,
+    Priority,
+}
+
+impl SortKey {
+    pub const NAMES: [&'static str; 5] = ["summary", "category", "created", "due", "priority"];
+
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "summary" => Some(SortKey::Summary),
+            "category" | "cat" => Some(SortKey::Category),
+            "created" => Some(SortKey::Created),
+            "due" => Some(SortKey::Due),
+            "priority" => Some(SortKey::Priority),
+            _ => None
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortKey::Summary => "summary",
+            SortKey::Category => "category",
+            SortKey::Created => "created",
+            SortKey::Due => "due",
+            SortKey::Priority => "priority",
+        }
+    }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Copy, Deserialize, Serialize)]
+pub struct SortOrder {
+    pub key: SortKey,
+    #[serde(default)]
+    pub descending: bool,
+}
+
+impl SortOrder {
+    pub fn parse(args: &str) -> Option<Self> {
+        // Takes a key with an optional direction, like "due desc"
+        let mut words = args.split_whitespace();
+        let key = SortKey::parse(words.next()?)?;
+        let descending = match words.next() {
+            None | Some("asc") => false,
+            Some("desc") => true,
+            Some(_) => return None
+        };
+
+        Some(Self { key, descending })
+    }
+
+    pub fn compare(&self, a: &Task, b: &Task) -> Ordering {
+        // Ascending is A to Z, oldest to newest, soonest due first, and
+        // highest priority first. Tasks missing the field go last either way
+        let descending = self.descending;
+
+        match self.key {
+            SortKey::Summary => compare_fields(
+                Some(a.summary.to_lowercase()),
+                Some(b.summary.to_lowercase()),
+                descending
+            ),
+            SortKey::Category => compare_fields(
+                a.category.as_ref().map(|c| c.to_lowercase()),
+                b.category.as_ref().map(|c| c.to_lowercase()),
+                descending
+            ),
+            SortKey::Created => compare_fields(a.created, b.created, descending),
+            SortKey::Due => compare_fields(a.due, b.due, descending),
+            SortKey::Priority => compare_fields(a.priority, b.priority, descending),
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.descending {
+            write!(f, "{} desc", self.key.name())
+        } else {
+            write!(f, "{}", self.key.name())
+        }
+    }
+}
+
+fn compare_fields<T: Ord>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) if descending => b.cmp(&a),
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CardMode {
+    Compact,
+    #[default]
+    Normal,
+    Detailed,
+}
+
+impl CardMode {
+    pub fn next(self) -> Self {
+        match self {
+            CardMode::Compact => CardMode::Normal,
+            CardMode::Normal => CardMode::Detailed,
+            CardMode::Detailed => CardMode::Compact,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArchiveGrouping {
+    #[default]
+    Week,
+    Month,
+}
+
+impl ArchiveGrouping {
+    pub fn next(self) -> Self {
+        match self {
+            ArchiveGrouping::Week => ArchiveGrouping::Month,
+            ArchiveGrouping::Month => ArchiveGrouping::Week,
+        }
+    }
+
+    pub fn label(self, task: &Task) -> String {
+        let date = match task.archived_at {
+            Some(archived_at) => archived_at.date_naive(),
+            None => return "Undated".to_string()
+        };
+
+        match self {
+            ArchiveGrouping::Week => format!(
+                "Week of {}",
+                date.week(Weekday::Mon).first_day().format("%Y-%m-%d")
+            ),
+            ArchiveGrouping::Month => date.format("%B %Y").to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 pub struct TaskList {
     pub name: String,
     pub color_index: u8,
+    #[serde(default)]
+    pub card_mode: CardMode,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wip_limit: Option<usize>,
+    #[serde(default)]
+    pub collapsed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_sort: Option<SortOrder>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_after_days: Option<u32>,
     #[serde(skip)]
     pub state: ListState,
+    #[serde(skip)]
+    pub offset: usize,
+    #[serde(skip)]
+    pub visible: usize,
     pub tasks: Vec<Task>,
 }
 
@@ -23,7 +542,14 @@ impl TaskList {
         Self {
             name: "Tasks".to_string(),
             color_index: 7,
+            card_mode: CardMode::default(),
+            wip_limit: None,
+            collapsed: false,
+            auto_sort: None,
+            archive_after_days: None,
             state: ListState::default(),
+            offset: 0,
+            visible: 0,
             tasks: Vec::new(),
         }
     }
@@ -32,7 +558,14 @@ impl TaskList {
         Self {
             name,
             color_index: 7,
+            card_mode: CardMode::default(),
+            wip_limit: None,
+            collapsed: false,
+            auto_sort: None,
+            archive_after_days: None,
             state: ListState::default(),
+            offset: 0,
+            visible: 0,
             tasks: Vec::new(),
         }
     }
@@ -41,7 +574,14 @@ impl TaskList {
         Self {
             name: "Backlog".to_string(),
             color_index: 6,
+            card_mode: CardMode::default(),
+            wip_limit: None,
+            collapsed: false,
+            auto_sort: None,
+            archive_after_days: None,
             state: ListState::default(),
+            offset: 0,
+            visible: 0,
             tasks: Vec::new(),
         }
     }
@@ -50,7 +590,14 @@ impl TaskList {
         Self {
             name: "Archive".to_string(),
             color_index: 1,
+            card_mode: CardMode::default(),
+            wip_limit: None,
+            collapsed: false,
+            auto_sort: None,
+            archive_after_days: None,
             state: ListState::default(),
+            offset: 0,
+            visible: 0,
             tasks: Vec::new(),
         }
     }
@@ -59,6 +606,62 @@ impl TaskList {
         self.tasks.is_empty()
     }
 
+    pub fn is_over_limit(&self) -> bool {
+        match self.wip_limit {
+            Some(limit) => self.len() > limit,
+            None => false
+        }
+    }
+
+    pub fn would_exceed_limit(&self, incoming: usize) -> bool {
+        match self.wip_limit {
+            Some(limit) => self.len() + incoming > limit,
+            None => false
+        }
+    }
+
+    pub fn count_label(&self) -> String {
+        match self.wip_limit {
+            Some(limit) => format!("{}/{}", self.len(), limit),
+            None => self.len().to_string()
+        }
+    }
+
+    pub fn total_estimate(&self) -> Option<f64> {
+        // Only lists where something was estimated have a total
+        let estimates: Vec<f64> = self.tasks.iter().filter_map(|task| task.estimate).collect();
+        if estimates.is_empty() {
+            None
+        } else {
+            Some(estimates.iter().sum())
+        }
+    }
+
+    pub fn title(&self, unit: EstimateUnit) -> String {
+        // Like "Doing (3/4, 8 pts) [due desc]", with the count shown for
+        // limited or estimated lists and the order for auto-sorted ones
+        let mut title = self.name.clone();
+        match self.total_estimate() {
+            Some(estimate) => {
+                title = format!("{} ({}, {})", title, self.count_label(), unit.format(estimate));
+            },
+            None if self.wip_limit.is_some() => {
+                title = format!("{} ({})", title, self.count_label());
+            },
+            None => {}
+        }
+        if let Some(order) = self.auto_sort {
+            title = format!("{} [{}]", title, order);
+        }
+
+        title
+    }
+
+    pub fn collapsed_width(&self) -> u16 {
+        // Room for the count between the border tees on either side
+        self.count_label().len() as u16 + 4
+    }
+
     pub fn select(&mut self, index: Option<usize>) {
         self.state.select(index);
     }
@@ -83,6 +686,108 @@ impl TaskList {
         self.tasks.push(task);
     }
 
+    pub fn add(&mut self, task: Task) -> usize {
+        // Tasks go to the bottom, or into place when the list is auto-sorted,
+        // and the index they end up at is returned
+        self.add_at(self.len(), task)
+    }
+
+    pub fn add_at(&mut self, i: usize, task: Task) -> usize {
+        let i = match self.auto_sort {
+            Some(order) => self.tasks
+                .iter()
+                .position(|t| order.compare(&task, t) == Ordering::Less)
+                .unwrap_or(self.len()),
+            None => i
+        };
+
+        // The selection stays on the same task
+        if let Some(selected) = self.get_selected_index() {
+            if i <= selected {
+                self.select(Some(selected + 1));
+            }
+        }
+
+        self.tasks.insert(i, task);
+        i
+    }
+
+    pub fn take_finished_tasks(&mut self) -> Vec<Task> {
+        // Takes out the tasks that have been in the list for longer than its
+        // archive age. Tasks from before the list had one start their clock now
+        let age = match self.archive_after_days {
+            Some(days) => TimeDelta::days(days as i64),
+            None => return Vec::new()
+        };
+
+        let now = Local::now();
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < self.len() {
+            if now - *self.tasks[i].moved_in.get_or_insert(now) >= age {
+                finished.push(self.remove(i));
+            } else {
+                i += 1;
+            }
+        }
+
+        if self.is_empty() {
+            self.select(None);
+        } else if let Some(i) = self.get_selected_index() {
+            self.select(Some(cmp::min(i, self.len() - 1)));
+        }
+
+        finished
+    }
+
+    pub fn take_old_tasks(&mut self, keep: Option<usize>, max_age_days: Option<u32>) -> Vec<Task> {
+        // Takes out the tasks past the newest `keep`, or archived longer ago
+        // than the maximum age. Tasks without an archive date are never taken
+        let now = Local::now();
+        let mut dated: Vec<usize> = (0..self.len())
+            .filter(|i| self.tasks[*i].archived_at.is_some())
+            .collect();
+        dated.sort_by_key(|i| cmp::Reverse(self.tasks[*i].archived_at));
+
+        let mut old = vec![false; self.len()];
+        for (rank, i) in dated.into_iter().enumerate() {
+            let archived_at = self.tasks[i].archived_at.unwrap_or(now);
+            let too_many = keep.is_some_and(|keep| rank >= keep);
+            let too_old = max_age_days
+                .is_some_and(|days| now - archived_at > TimeDelta::days(days as i64));
+            old[i] = too_many || too_old;
+        }
+
+        let mut taken = Vec::new();
+        for (i, task) in std::mem::take(&mut self.tasks).into_iter().enumerate() {
+            if old[i] {
+                taken.push(task);
+            } else {
+                self.tasks.push(task);
+            }
+        }
+        self.select(if self.is_empty() { None } else { Some(0) });
+
+        taken
+    }
+
+    pub fn resort(&mut self) {
+        if let Some(order) = self.auto_sort {
+            self.sort(order);
+        }
+    }
+
+    pub fn sort(&mut self, order: SortOrder) {
+        // Sorting is stable, and the selection stays with the same task
+        let mut tasks: Vec<(usize, Task)> = self.tasks.drain(..).enumerate().collect();
+        tasks.sort_by(|(_, a), (_, b)| order.compare(a, b));
+
+        let selected = self.get_selected_index()
+            .and_then(|selected| tasks.iter().position(|(i, _)| *i == selected));
+        self.tasks = tasks.into_iter().map(|(_, task)| task).collect();
+        self.select(selected);
+    }
+
     pub fn insert(&mut self, i: usize, task: Task) {
         self.tasks.insert(i, task);
     }
@@ -138,16 +843,90 @@ impl ProjectList {
     }
 }
 
+pub struct Picker {
+    pub title: String,
+    pub items: Vec<String>,
+    pub input: Input,
+    pub state: ListState,
+}
+
+impl Picker {
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            items: Vec::new(),
+            input: Input::new()
+                .name("Filter".to_string()),
+            state: ListState::default(),
+        }
+    }
+
+    pub fn from(title: String, items: Vec<String>) -> Self {
+        let mut picker = Self {
+            title,
+            items,
+            ..Self::new()
+        };
+        picker.reset_selection();
+
+        picker
+    }
+
+    pub fn get_matches(&self) -> Vec<usize> {
+        // Items match if they contain the filter's characters in order,
+        // ignoring case, so "dng" finds "Doing"
+        let filter = self.input.text.to_lowercase();
+
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                let item = item.to_lowercase();
+                let mut chars = item.chars();
+                filter.chars().all(|c| chars.any(|item_c| item_c == c))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn reset_selection(&mut self) {
+        if self.get_matches().is_empty() {
+            self.state.select(None);
+        } else {
+            self.state.select(Some(0));
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let num_matches = self.get_matches().len();
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + 1) % num_matches));
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        let num_matches = self.get_matches().len();
+        if let Some(i) = self.state.selected() {
+            self.state.select(Some((i + num_matches - 1) % num_matches));
+        }
+    }
+
+    pub fn get_selected_item(&self) -> Option<usize> {
+        // The index of the chosen item among all items, not just the matches
+        self.state
+            .selected()
+            .and_then(|i| self.get_matches().get(i).copied())
+    }
+}
+
 fn get_projects() -> Result<Vec<String>, std::io::Error> {
     let mut dirs = Vec::new();
     let paths = fs::read_dir("./")?;
-    for path in paths {
-        if let Ok(path) = path {
-            if path.path().is_dir() {
-                if let Some(filename) = path.path().to_str() {
-                    let filename = &filename.to_string()[2..];
-                    dirs.push(filename.to_string());
-                }
+    for path in paths.flatten() {
+        if path.path().is_dir() {
+            if let Some(filename) = path.path().to_str() {
+                let filename = &filename.to_string()[2..];
+                dirs.push(filename.to_string());
             }
         }
     }
@@ -1,3 +1,4 @@
+use chrono::{Local, NaiveDate, TimeDelta};
 use crossterm::{
     event::{
         DisableMouseCapture,
@@ -28,12 +29,17 @@ use tui::{
 };
 
 mod app;
+mod commands;
+mod config;
 mod events;
 mod inputs;
+mod keymap;
 mod lists;
+mod stats;
 mod ui;
 
 use app::*;
+use config::*;
 use events::*;
 use ui::*;
 
@@ -42,26 +48,40 @@ type Result<T> = std::result::Result<T, Box<dyn Error>>;
 fn main() -> Result<()> {
     let args = get_command_line_args();
     setup_project_path()?;
+    let config = load_config();
+
+    // Subcommands do their work without opening the interface
+    if args[1] == "archive" && args.len() > 2 {
+        run_archive_command(&args[2..], &config);
+        return Ok(());
+    }
+    if args[1] == "report" && args.len() > 2 {
+        run_report_command(&args[2..]);
+        return Ok(());
+    }
+    if args[1] == "timesheet" && args.len() > 2 {
+        run_timesheet_command(&args[2..]);
+        return Ok(());
+    }
 
     // Panic Handling
     chain_hook();
 
     // Setup Terminal
-    let mut terminal = init_terminal()?;
+    let mut terminal = init_terminal(config.mouse)?;
     terminal.clear()?;
 
     // Application Entry Point
-    let res: io::Result<()>;
-    if project_exists(&args[1])? {
-        let mut app = App::create(args[1].clone())?;
-        res = run_app(&mut terminal, &mut app);
+    let res: io::Result<()> = if project_exists(&args[1])? {
+        let mut app = App::create(args[1].clone(), config)?;
+        run_app(&mut terminal, &mut app)
     } else {
-        res = Err(io::Error::new(
+        Err(io::Error::new(
                 io::ErrorKind::NotFound,
                 format!("Project '{}' does not exist.\nTo create it, run 'kadai' with no arguments, then press 'n'.", &args[1])
             )
-        );
-    }
+        )
+    };
 
 
     // Restore Terminal
@@ -115,6 +135,100 @@ fn get_kadai_directory(path: PathBuf) -> Result<PathBuf> {
     Ok(path)
 }
 
+fn load_config() -> Config {
+    Config::load().unwrap_or_else(|err| {
+        eprintln!("Could not load config: {}", err);
+        process::exit(1);
+    })
+}
+
+fn run_archive_command(args: &[String], config: &Config) {
+    let project = match args {
+        [command, project] if command == "prune" => project,
+        _ => {
+            eprintln!("Usage: kadai archive prune <project>");
+            process::exit(1);
+        }
+    };
+
+    match prune_archive(project, config) {
+        Ok(summary) => println!("{}", summary),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn run_report_command(args: &[String]) {
+    let project = &args[0];
+    let mut since = None;
+    let mut until = None;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--since" => since = Some(rest.next().unwrap_or_else(|| report_usage())),
+            "--until" => until = Some(rest.next().unwrap_or_else(|| report_usage())),
+            _ => report_usage()
+        }
+    }
+
+    match report_changes(project, parse_since(since), until.map(parse_date)) {
+        Ok(report) => println!("{}", report),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn report_usage() -> ! {
+    eprintln!("Usage: kadai report <project> [--since YYYY-MM-DD] [--until YYYY-MM-DD]");
+    process::exit(1);
+}
+
+fn run_timesheet_command(args: &[String]) {
+    let project = &args[0];
+    let mut since = None;
+    let mut csv = false;
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--since" => since = Some(rest.next().unwrap_or_else(|| timesheet_usage())),
+            "--csv" => csv = true,
+            _ => timesheet_usage()
+        }
+    }
+
+    match export_timesheet(project, parse_since(since), csv) {
+        Ok(timesheet) => println!("{}", timesheet),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn timesheet_usage() -> ! {
+    eprintln!("Usage: kadai timesheet <project> [--since YYYY-MM-DD] [--csv]");
+    process::exit(1);
+}
+
+fn parse_since(since: Option<&String>) -> NaiveDate {
+    // Reports cover the last week unless told otherwise
+    match since {
+        Some(since) => parse_date(since),
+        None => Local::now().date_naive() - TimeDelta::days(7)
+    }
+}
+
+fn parse_date(date: &String) -> NaiveDate {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap_or_else(|_| {
+        eprintln!("'{}' isn't a date like 2024-12-31", date);
+        process::exit(1);
+    })
+}
+
 fn project_exists(project: &String) -> Result<bool> {
     let mut path = env::current_dir()?;
     path.push(project);
@@ -132,7 +246,10 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<
         handle_events(app)?;
     }
 
-    app.save_changes()?;
+    if !app.discard_changes {
+        app.save_changes()?;
+    }
+    app.close_project()?;
 
     Ok(())
 }
@@ -146,17 +263,21 @@ pub fn chain_hook() {
     }));
 }
 
-pub fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
+pub fn init_terminal(mouse: bool) -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(
         stdout,
         EnterAlternateScreen,
-        EnableMouseCapture,
         PushKeyboardEnhancementFlags(
             KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
         )
     )?;
+    // Capturing the mouse stops the terminal from selecting text, so it can
+    // be turned off in the config for those who'd rather have that
+    if mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
 
@@ -0,0 +1,287 @@
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, Weekday};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Reverse, collections::{BTreeMap, HashMap}};
+
+use crate::app::ListRef;
+use crate::lists::*;
+
+pub struct Statistics {
+    pub list_times: Vec<(String, Option<TimeDelta>)>,
+    pub lead_times: Vec<TimeDelta>,
+    pub cycle_times: Vec<TimeDelta>,
+    pub throughput: Vec<u64>,
+    pub categories: Vec<(String, u64)>,
+}
+
+impl Statistics {
+    pub fn new(task_lists: &[TaskList], backlog: &TaskList, archive: &TaskList, weeks: usize) -> Self {
+        let mut stays: BTreeMap<&str, Vec<TimeDelta>> = BTreeMap::new();
+        for (list, task) in project_tasks(task_lists, backlog, archive) {
+            for stay in list_stays(task, list) {
+                if let Some(to) = stay.to {
+                    stays.entry(stay.list).or_default().push(to - stay.from);
+                }
+            }
+        }
+        let list_times = task_lists
+            .iter()
+            .map(|list| {
+                let average = stays.get(list.name.as_str()).and_then(|times| average(times));
+                (list.name.clone(), average)
+            })
+            .collect();
+
+        let mut lead_times = Vec::new();
+        let mut cycle_times = Vec::new();
+        for task in &archive.tasks {
+            let archived_at = match task.archived_at {
+                Some(archived_at) => archived_at,
+                None => continue
+            };
+            if let Some(created) = task.created {
+                lead_times.push(archived_at - created);
+            }
+            if let Some(started) = first_move(task) {
+                cycle_times.push(archived_at - started);
+            }
+        }
+
+        // Oldest week first, ending with the current one
+        let mut throughput = vec![0; weeks];
+        let this_week = Local::now().date_naive().week(Weekday::Mon).first_day();
+        for archived_at in archive.tasks.iter().filter_map(|task| task.archived_at) {
+            let week = archived_at.date_naive().week(Weekday::Mon).first_day();
+            let weeks_ago = (this_week - week).num_weeks();
+            if weeks_ago >= 0 && (weeks_ago as usize) < weeks {
+                throughput[weeks - 1 - weeks_ago as usize] += 1;
+            }
+        }
+
+        let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+        for task in task_lists.iter().chain([backlog]).flat_map(|list| &list.tasks) {
+            let category = task.category.clone().unwrap_or_else(|| "None".to_string());
+            *counts.entry(category).or_default() += 1;
+        }
+        let mut categories: Vec<(String, u64)> = counts.into_iter().collect();
+        categories.sort_by_key(|(_, count)| Reverse(*count));
+
+        Self {
+            list_times,
+            lead_times,
+            cycle_times,
+            throughput,
+            categories,
+        }
+    }
+}
+
+pub struct FlowHistory {
+    pub start: NaiveDate,
+    pub list_counts: Vec<Vec<u64>>,
+    pub archived: Vec<u64>,
+}
+
+impl FlowHistory {
+    // The number of tasks in each tracker list at the end of every day, going
+    // back to when the first of them was added, up to a limit
+    pub fn new(task_lists: &[TaskList], backlog: &TaskList, archive: &TaskList, max_days: usize) -> Self {
+        let today = Local::now().date_naive();
+        let names: Vec<&str> = task_lists.iter().map(|list| list.name.as_str()).collect();
+
+        let stays: Vec<Stay> = project_tasks(task_lists, backlog, archive)
+            .flat_map(|(list, task)| list_stays(task, list))
+            .filter(|stay| names.contains(&stay.list))
+            .collect();
+
+        let earliest = today - TimeDelta::days(max_days as i64 - 1);
+        let start = stays
+            .iter()
+            .map(|stay| stay.from.date_naive())
+            .min()
+            .unwrap_or(today)
+            .clamp(earliest, today);
+        let days: Vec<NaiveDate> = start.iter_days().take_while(|day| *day <= today).collect();
+
+        let mut list_counts = vec![vec![0; days.len()]; names.len()];
+        for stay in &stays {
+            let list = match names.iter().position(|name| *name == stay.list) {
+                Some(list) => list,
+                None => continue
+            };
+            for (i, day) in days.iter().enumerate() {
+                let entered = stay.from.date_naive() <= *day;
+                let left = stay.to.is_some_and(|to| to.date_naive() <= *day);
+                if entered && !left {
+                    list_counts[list][i] += 1;
+                }
+            }
+        }
+
+        let archived = days
+            .iter()
+            .map(|day| {
+                archive.tasks
+                    .iter()
+                    .filter(|task| task.archived_at.is_some_and(|at| at.date_naive() <= *day))
+                    .count() as u64
+            })
+            .collect();
+
+        Self {
+            start,
+            list_counts,
+            archived,
+        }
+    }
+
+    pub fn days(&self) -> usize {
+        self.archived.len()
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Snapshot {
+    pub taken: DateTime<Local>,
+    pub lists: Vec<ListSnapshot>,
+    pub backlog: ListSnapshot,
+    pub archive: ListSnapshot,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ListSnapshot {
+    pub name: String,
+    pub size: usize,
+    pub tasks: Vec<u64>,
+}
+
+impl Snapshot {
+    pub fn new(task_lists: &[TaskList], backlog: &TaskList, archive: &TaskList) -> Self {
+        Self {
+            taken: Local::now(),
+            lists: task_lists.iter().map(ListSnapshot::new).collect(),
+            backlog: ListSnapshot::new(backlog),
+            archive: ListSnapshot::new(archive),
+        }
+    }
+
+    pub fn all_lists(&self) -> impl Iterator<Item = (ListRef, &ListSnapshot)> {
+        self.lists
+            .iter()
+            .enumerate()
+            .map(|(i, list)| (ListRef::Tracker(i), list))
+            .chain([(ListRef::Backlog, &self.backlog), (ListRef::Archive, &self.archive)])
+    }
+
+    pub fn get_list(&self, list_ref: ListRef) -> Option<&ListSnapshot> {
+        match list_ref {
+            ListRef::Tracker(i) => self.lists.get(i),
+            ListRef::Backlog => Some(&self.backlog),
+            ListRef::Archive => Some(&self.archive),
+        }
+    }
+
+    // Where each task was when the snapshot was taken, by the list's
+    // position so that renaming a list doesn't look like a move
+    pub fn locations(&self) -> HashMap<u64, ListRef> {
+        self.all_lists()
+            .flat_map(|(list_ref, list)| list.tasks.iter().map(move |id| (*id, list_ref)))
+            .collect()
+    }
+}
+
+impl ListSnapshot {
+    fn new(list: &TaskList) -> Self {
+        Self {
+            name: list.name.clone(),
+            size: list.len(),
+            tasks: list.tasks.iter().filter_map(|task| task.id).collect(),
+        }
+    }
+}
+
+fn project_tasks<'a>(
+    task_lists: &'a [TaskList],
+    backlog: &'a TaskList,
+    archive: &'a TaskList
+) -> impl Iterator<Item = (&'a str, &'a Task)> {
+    task_lists
+        .iter()
+        .chain([backlog, archive])
+        .flat_map(|list| list.tasks.iter().map(move |task| (list.name.as_str(), task)))
+}
+
+struct Stay<'a> {
+    list: &'a str,
+    from: DateTime<Local>,
+    to: Option<DateTime<Local>>,
+}
+
+// Every stay of the task in a list, read from its history, with no end for the
+// one it's still in. Moves from before history was kept are unknown, so the
+// task counts as having been in its first recorded list since it was created
+fn list_stays<'a>(task: &'a Task, current: &'a str) -> Vec<Stay<'a>> {
+    let mut stays = Vec::new();
+    let mut here: Option<(&str, DateTime<Local>)> = None;
+    let mut first = true;
+
+    for entry in &task.history {
+        let (left, entered) = match &entry.event {
+            TaskEvent::Created { list } => (None, Some(list.as_str())),
+            TaskEvent::Moved { from, to } => (Some(from.as_str()), Some(to.as_str())),
+            TaskEvent::Restored { to } => (None, Some(to.as_str())),
+            TaskEvent::Backlogged { from } | TaskEvent::Archived { from } => {
+                (Some(from.as_str()), None)
+            },
+            TaskEvent::Edited { .. } => continue,
+        };
+
+        if first {
+            here = left.zip(task.created);
+            first = false;
+        }
+        if let Some((list, since)) = here.take() {
+            stays.push(Stay { list, from: since, to: Some(entry.at) });
+        }
+        here = entered.map(|list| (list, entry.at));
+    }
+
+    if first {
+        here = task.moved_in.or(task.created).map(|since| (current, since));
+    }
+    if let Some((list, since)) = here {
+        stays.push(Stay { list, from: since, to: None });
+    }
+
+    stays
+}
+
+fn first_move(task: &Task) -> Option<DateTime<Local>> {
+    task.history
+        .iter()
+        .find(|entry| matches!(entry.event, TaskEvent::Moved { .. }))
+        .map(|entry| entry.at)
+}
+
+pub fn average(times: &[TimeDelta]) -> Option<TimeDelta> {
+    if times.is_empty() {
+        return None;
+    }
+
+    let total: TimeDelta = times.iter().sum();
+    Some(total / times.len() as i32)
+}
+
+pub fn median(times: &[TimeDelta]) -> Option<TimeDelta> {
+    let mut times = times.to_vec();
+    times.sort();
+
+    match times.len() {
+        0 => None,
+        n if n % 2 == 0 => Some((times[n / 2 - 1] + times[n / 2]) / 2),
+        n => Some(times[n / 2])
+    }
+}
+
+pub fn format_days(time: TimeDelta) -> String {
+    format!("{:.1} days", time.num_minutes() as f64 / (24.0 * 60.0))
+}
@@ -1,3 +1,4 @@
+use chrono::{NaiveDate, TimeDelta};
 use std::{
     borrow::Cow,
     cmp
@@ -15,16 +16,23 @@ use tui::{
         Rect
     },
     style::{Color, Modifier, Style},
-    symbols::line,
+    symbols::{line, Marker},
     text::{Span, Spans},
     widgets::{
+        Axis,
+        BarChart,
         Block,
         Borders,
         BorderType,
+        Chart,
         Clear,
+        Dataset,
+        GraphType,
         List,
         ListItem,
+        ListState,
         Paragraph,
+        Sparkline,
         Widget,
         Wrap
     },
@@ -33,24 +41,29 @@ use tui::{
 
 use crate::app::*;
 use crate::inputs::*;
+use crate::keymap::*;
 use crate::lists::*;
+use crate::stats::*;
 
 macro_rules! raw_para {
     ( $( $x:expr ),* ) => {
         {
-            let mut temp_para = Vec::new();
-            $(
-                temp_para.push(
+            vec![
+                $(
                     Spans::from(
                         Span::raw($x)
-                    )
-                );
-            )*
-            temp_para
+                    ),
+                )*
+            ]
         }
     };
 }
 
+enum CardRow {
+    Header(String),
+    Task(usize),
+}
+
 struct CustomBorder {
     title: String,
     title_style: Style,
@@ -87,28 +100,34 @@ impl Widget for CustomBorder {
         // Border Lines
         let mut line = String::new();
         line.push_str(line::VERTICAL_RIGHT);
-        for _ in 0..area.width - 2 {
+        for _ in 0..area.width.saturating_sub(2) {
             line.push_str(line::HORIZONTAL);
         }
         line.push_str(line::VERTICAL_LEFT);
         buf.set_string(area.left(), area.top(), line.clone(), self.border_style);
         buf.set_string(area.left(), area.bottom() - 1, line, self.border_style);
 
-        // Title
-        let offset = area.width / 2 - self.title.len() as u16 / 2;
+        // Title, cut down to leave room for its tee's and the corners
+        let limit = area.width.saturating_sub(4) as usize;
+        if limit <= 3 {
+            return;
+        }
+        let title = shorten(&self.title, limit + 1, limit - 3);
+        let title_width = title.chars().count() as u16;
+        let offset = (area.width / 2).saturating_sub(title_width / 2);
         let title_x = area.left() + offset;
         let title_y = area.y;
-        buf.set_string(title_x, title_y, self.title.clone(), self.title_style);
+        buf.set_string(title_x, title_y, title, self.title_style);
 
         // Title Tee's
         buf.set_string(
-            title_x - 1,
+            title_x.saturating_sub(1),
             area.top(),
             line::VERTICAL_LEFT,
             self.border_style
         );
         buf.set_string(
-            title_x + self.title.len() as u16,
+            title_x + title_width,
             area.top(),
             line::VERTICAL_RIGHT,
             self.border_style
@@ -117,9 +136,13 @@ impl Widget for CustomBorder {
 }
 
 pub fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App, state: AppState) {
-    // If the width of the terminal is too small, just render a blank screen
-    if frame.size().width <= 50 {
+    // If the terminal can't fit even a single list, say so instead
+    if frame.size().width < MIN_LIST_WIDTH {
+        let message = Paragraph::new("Terminal too narrow")
+            .wrap(Wrap { trim: true })
+            .alignment(Alignment::Center);
         frame.render_widget(Clear, frame.size());
+        frame.render_widget(message, frame.size());
         return;
     }
 
@@ -146,10 +169,8 @@ pub fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App, state: AppState) {
             ui(frame, app, *prev);
             render_list_popup(frame, app);
         },
-        AppState::ArchivePopup(prev) => {
-            ui(frame, app, *prev);
-            render_list_popup(frame, app);
-        },
+        AppState::ArchivePopup(_) => render_archive(frame, app),
+        AppState::ArchiveSearch(prev) => ui(frame, app, *prev),
         AppState::EditTask(prev) => {
             ui(frame, app, *prev);
             render_task_editor(frame, app, "Edit Task Details".to_string());
@@ -160,20 +181,63 @@ pub fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App, state: AppState) {
         },
         AppState::DeleteTask(prev) => {
             ui(frame, app, *prev);
-            render_prompt(frame, "Delete Highlighted Task?".to_string());
+            let prompt = match app.num_marked_tasks() {
+                0 => "Delete Highlighted Task?".to_string(),
+                1 => "Delete 1 Marked Task?".to_string(),
+                n => format!("Delete {} Marked Tasks?", n)
+            };
+            render_prompt(frame, prompt);
+        },
+        AppState::SetCategory(prev) => {
+            ui(frame, app, *prev);
+            render_single_input_editor(frame, app, "Set Category".to_string());
+        },
+        AppState::SendToProject(prev) => {
+            ui(frame, app, *prev);
+            render_picker(frame, app);
+        },
+        AppState::SendToProjectList(prev) => {
+            ui(frame, app, *prev);
+            render_picker(frame, app);
+        },
+        AppState::MoveToList(prev) => {
+            ui(frame, app, *prev);
+            render_picker(frame, app);
+        },
+        AppState::CommandLine(prev) => {
+            ui(frame, app, *prev);
+            render_command_line(frame, app);
         },
         AppState::EditList(prev) => {
             ui(frame, app, *prev);
-            render_single_input_editor(frame, app, "Edit List Details".to_string());
+            render_list_editor(frame, app, "Edit List Details".to_string());
         },
         AppState::CreateList(prev) => {
             ui(frame, app, *prev);
-            render_single_input_editor(frame, app, "Create New List".to_string());
+            render_list_editor(frame, app, "Create New List".to_string());
         },
         AppState::DeleteList(prev) => {
             ui(frame, app, *prev);
             render_prompt(frame, "Delete Focused List?".to_string());
         },
+        AppState::ConfirmMove(prev) => {
+            ui(frame, app, *prev);
+            let prompt = format!("Exceed WIP Limit of '{}'?", app.get_pending_move_list_name());
+            render_prompt(frame, prompt);
+        },
+        AppState::Statistics(_) => render_statistics(frame, app),
+        AppState::FlowCharts(_) => render_flow_charts(frame, app),
+        AppState::SetBurndownTarget(prev) => {
+            ui(frame, app, *prev);
+            render_single_input_editor(frame, app, "Set Burndown Target".to_string());
+        },
+        AppState::Help(prev) => {
+            ui(frame, app, *prev);
+            render_help(frame, app);
+        },
+        AppState::HelpSearch(prev) => {
+            ui(frame, app, *prev);
+        },
     }
 }
 
@@ -221,10 +285,14 @@ fn render_project_menu<B: Backend>(
     let list_area = centered_rect(40, 100, chunks[1]);
 
     if app.project_list.is_empty() {
+        let create = match app.config.keymap.key_for(Mode::ProjectMenu, Action::NewProject) {
+            Some(key) => format!("Hit '{}' to create and open a new project.", key),
+            None => String::new()
+        };
         let mut commands = raw_para!(
             "There are currently no projects.",
             "",
-            "Hit 'n' to create and open a new project."
+            create
         );
 
         for _ in 0..chunks[1].height / 2 - 2 {
@@ -385,32 +453,61 @@ fn render_tracker<B: Backend>(
         )
         .split(size);
 
+    app.update_list_viewport(size.width);
+
     render_info_bar(frame, app, chunks[0]);
 
-    let list_width = size.width / app.task_lists.len() as u16;
+    app.list_areas.clear();
+    app.lane_areas.clear();
+    app.card_areas.clear();
+
+    // Collapsed lists get a narrow strip, and the rest of the width is shared
+    // by the others, with the middle one soaking up any rounding leftovers
+    let list_nums: Vec<usize> = (app.list_offset..app.list_offset + app.visible_lists).collect();
+    let expanded: Vec<usize> = list_nums
+        .iter()
+        .copied()
+        .filter(|list_num| !app.is_list_collapsed(*list_num))
+        .collect();
+    let collapsed_width: u16 = list_nums
+        .iter()
+        .filter(|list_num| app.is_list_collapsed(**list_num))
+        .map(|list_num| app.task_lists[*list_num].collapsed_width())
+        .sum();
+    let list_width = match expanded.len() {
+        0 => 0,
+        n => size.width.saturating_sub(collapsed_width) / n as u16
+    };
+    let middle = expanded.get(expanded.len() / 2).copied();
 
     let mut constraints: Vec<Constraint> = Vec::new();
-    for _ in 0..app.task_lists.len() / 2 {
-        constraints.push(Constraint::Length(list_width));
-    }
-    constraints.push(Constraint::Min(10));
-    if app.task_lists.len() % 2 == 0 {
-        for _ in 0..app.task_lists.len() / 2 - 1 {
-            constraints.push(Constraint::Length(list_width));
-        }
-    } else {
-        for _ in 0..app.task_lists.len() / 2 {
+    for list_num in list_nums.iter() {
+        if app.is_list_collapsed(*list_num) {
+            constraints.push(Constraint::Length(app.task_lists[*list_num].collapsed_width()));
+        } else if Some(*list_num) == middle {
+            constraints.push(Constraint::Min(10));
+        } else {
             constraints.push(Constraint::Length(list_width));
         }
     }
+    if expanded.is_empty() {
+        constraints.push(Constraint::Min(0));
+    }
 
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints(constraints.as_ref())
+        .constraints(constraints.as_slice())
         .split(chunks[1]);
 
-    for i in 0..app.task_lists.len() {
-        render_task_list(frame, app, chunks[i], i);
+    for (list_num, chunk) in list_nums.into_iter().zip(chunks) {
+        app.list_areas.push((list_num, chunk));
+        if app.is_list_collapsed(list_num) {
+            render_collapsed_list(frame, app, chunk, list_num);
+        } else if app.swimlanes {
+            render_swimlane_list(frame, app, chunk, list_num);
+        } else {
+            render_task_list(frame, app, chunk, list_num);
+        }
     }
 }
 
@@ -438,7 +535,18 @@ fn render_info_bar<B: Backend>(
         )
         .split(inner_area);
 
-    let left = Spans::from(vec![
+    let mut left = Vec::new();
+    let hidden_left = app.num_lists_hidden_left();
+    if hidden_left > 0 {
+        left.push(
+            Span::styled(
+                format!("◀ {} more | ", hidden_left),
+                Style::default()
+                .add_modifier(Modifier::BOLD)
+            )
+        );
+    }
+    left.extend(vec![
         Span::styled(
             "Project: ",
             Style::default()
@@ -452,13 +560,35 @@ fn render_info_bar<B: Backend>(
         ),
     ]);
 
-    let left = Paragraph::new(left)
+    if let Some(task) = app.get_timed_task() {
+        let mut summary = task.summary.clone();
+        if summary.chars().count() > 20 {
+            summary = format!("{}...", summary.chars().take(17).collect::<String>());
+        }
+        left.push(Span::raw(" | "));
+        left.push(
+            Span::styled(
+                format!("▶ {} {}", summary, format_time_spent(task.time_spent())),
+                Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD)
+            )
+        );
+    }
+
+    let left = Paragraph::new(Spans::from(left))
         .block(Block::default())
         .wrap(Wrap { trim: true });
 
     frame.render_widget(left, chunks[1]);
 
-    let right = Spans::from(vec![
+    // Estimates are summed next to each count once anything is estimated
+    let estimate_unit = app.config.estimate_unit;
+    let estimate = |total: Option<f64>| match total {
+        Some(total) => format!(" ({})", estimate_unit.format(total)),
+        None => String::new()
+    };
+    let mut right = vec![
         Span::styled(
             app.backlog.len().to_string(),
             Style::default()
@@ -466,7 +596,7 @@ fn render_info_bar<B: Backend>(
             .add_modifier(Modifier::BOLD)
         ),
         Span::styled(
-            " Backlogged",
+            format!(" Backlogged{}", estimate(app.backlog.total_estimate())),
             Style::default()
             .fg(Color::Indexed(app.backlog.color_index))
             .add_modifier(Modifier::BOLD)
@@ -478,7 +608,7 @@ fn render_info_bar<B: Backend>(
             .add_modifier(Modifier::BOLD)
         ),
         Span::styled(
-            " Tracked",
+            format!(" Tracked{}", estimate(app.tracked_estimate())),
             Style::default()
             .add_modifier(Modifier::BOLD)
         ),
@@ -490,24 +620,68 @@ fn render_info_bar<B: Backend>(
             .add_modifier(Modifier::BOLD)
         ),
         Span::styled(
-            " Archived",
+            format!(" Archived{}", estimate(app.archive.total_estimate())),
             Style::default()
             .fg(Color::Indexed(app.archive.color_index))
             .add_modifier(Modifier::BOLD)
         ),
-    ]);
+    ];
+    let hidden_right = app.num_lists_hidden_right();
+    if hidden_right > 0 {
+        right.push(
+            Span::styled(
+                format!(" | {} more ▶", hidden_right),
+                Style::default()
+                .add_modifier(Modifier::BOLD)
+            )
+        );
+    }
 
-    let right = Paragraph::new(right)
+    let right = Paragraph::new(Spans::from(right))
         .block(Block::default())
         .alignment(Alignment::Right)
         .wrap(Wrap { trim: true });
 
     frame.render_widget(right, chunks[1]);
 
-    if app.unsaved_changes {
+    if let Some(message) = &app.message {
+        let middle = Spans::from(vec![
+            Span::styled(
+                message.clone(),
+                Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD)
+            ),
+        ]);
+
+        let middle = Paragraph::new(middle)
+            .block(Block::default())
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(middle, chunks[1]);
+    } else if app.unsaved_changes
+        || app.has_marked_tasks()
+        || app.count.is_some()
+        || app.filter.is_some() {
+        let mut status = Vec::new();
+        if let Some(filter) = &app.filter {
+            status.push(format!("Filter: {}", filter));
+        }
+        if let Some(count) = app.count {
+            status.push(format!("Count: {}", count));
+        }
+        let num_marked = app.num_marked_tasks();
+        if num_marked > 0 {
+            status.push(format!("{} Marked", num_marked));
+        }
+        if app.unsaved_changes {
+            status.push("Unsaved Changes".to_string());
+        }
+
         let middle = Spans::from(vec![
             Span::styled(
-                "Unsaved Changes",
+                status.join(" | "),
                 Style::default()
                 .add_modifier(Modifier::BOLD)
             ),
@@ -557,17 +731,32 @@ fn render_task_data<B: Backend>(
             )
             .split(inner_area);
 
-        let description: String;
-        match &task.description {
-            Some(d) => description = d.to_string(),
-            None => description = "N/A".to_string()
-        }
-        let category: String;
-        match &task.category {
-            Some(c) => category = c.to_string(),
-            None => category = "N/A".to_string()
-        }
-        let details = vec![
+        let description = match &task.description {
+            Some(d) => d.to_string(),
+            None => "N/A".to_string()
+        };
+        let category = match &task.category {
+            Some(c) => c.to_string(),
+            None => "N/A".to_string()
+        };
+        let created = match &task.created {
+            Some(c) => c.format("%Y-%m-%d %H:%M").to_string(),
+            None => "N/A".to_string()
+        };
+        let due = match &task.due {
+            Some(d) => d.format("%Y-%m-%d").to_string(),
+            None => "N/A".to_string()
+        };
+        let priority = match &task.priority {
+            Some(p) => p.to_string(),
+            None => "N/A".to_string()
+        };
+        let due_style = if task.is_overdue() {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default()
+        };
+        let mut details = vec![
             Spans::from(
                 vec![
                     Span::styled(
@@ -589,18 +778,129 @@ fn render_task_data<B: Backend>(
                     Span::raw(category),
                 ]
             ),
-            Spans::from(Span::raw("")),
             Spans::from(
                 vec![
                     Span::styled(
-                        "Description: ",
+                        "Priority: ",
                         Style::default()
                         .add_modifier(Modifier::BOLD)
                     ),
-                    Span::raw(description),
+                    Span::raw(priority),
+                ]
+            ),
+            Spans::from(
+                vec![
+                    Span::styled(
+                        "Due: ",
+                        Style::default()
+                        .add_modifier(Modifier::BOLD)
+                    ),
+                    Span::styled(due, due_style),
+                ]
+            ),
+            Spans::from(
+                vec![
+                    Span::styled(
+                        "Created: ",
+                        Style::default()
+                        .add_modifier(Modifier::BOLD)
+                    ),
+                    Span::raw(created),
                 ]
             ),
         ];
+        if let Some(estimate) = task.estimate {
+            details.push(
+                Spans::from(
+                    vec![
+                        Span::styled(
+                            "Estimate: ",
+                            Style::default()
+                            .add_modifier(Modifier::BOLD)
+                        ),
+                        Span::raw(app.config.estimate_unit.format(estimate)),
+                    ]
+                )
+            );
+        }
+        if !task.time_sessions.is_empty() {
+            let running = if task.is_timing() { " (running)" } else { "" };
+            details.push(
+                Spans::from(
+                    vec![
+                        Span::styled(
+                            "Time Spent: ",
+                            Style::default()
+                            .add_modifier(Modifier::BOLD)
+                        ),
+                        Span::raw(format!("{}{}", format_time_spent(task.time_spent()), running)),
+                    ]
+                )
+            );
+        }
+        let archived = match (&task.archived_at, &task.archived_from) {
+            (Some(at), Some(from)) => Some(format!("{} from '{}'", at.format("%Y-%m-%d %H:%M"), from)),
+            (Some(at), None) => Some(at.format("%Y-%m-%d %H:%M").to_string()),
+            (None, Some(from)) => Some(format!("from '{}'", from)),
+            (None, None) => None
+        };
+        if let Some(archived) = archived {
+            details.push(
+                Spans::from(
+                    vec![
+                        Span::styled(
+                            "Archived: ",
+                            Style::default()
+                            .add_modifier(Modifier::BOLD)
+                        ),
+                        Span::raw(archived),
+                    ]
+                )
+            );
+        }
+        details.push(Spans::from(Span::raw("")));
+        details.push(
+            Spans::from(
+                vec![
+                    Span::styled(
+                        "Description: ",
+                        Style::default()
+                        .add_modifier(Modifier::BOLD)
+                    ),
+                    Span::raw(description),
+                ]
+            )
+        );
+        if !task.history.is_empty() {
+            details.push(Spans::from(Span::raw("")));
+            details.push(
+                Spans::from(
+                    Span::styled(
+                        "History:",
+                        Style::default()
+                        .add_modifier(Modifier::BOLD)
+                    )
+                )
+            );
+            for entry in task.history.iter() {
+                let by = match &entry.by {
+                    Some(user) => format!(" by {}", user),
+                    None => String::new()
+                };
+                details.push(
+                    Spans::from(
+                        vec![
+                            Span::styled(
+                                format!("{}  ", entry.at.format("%Y-%m-%d %H:%M")),
+                                Style::default()
+                                .fg(Color::Gray)
+                            ),
+                            Span::raw(format!("{}{}", entry.event, by)),
+                        ]
+                    )
+                );
+            }
+        }
         let details = Paragraph::new(details)
             .block(Block::default())
             .wrap(Wrap { trim: true })
@@ -608,9 +908,14 @@ fn render_task_data<B: Backend>(
 
         frame.render_widget(details, chunks[0]);
 
+        let hints = press_hints(
+            app,
+            Mode::TaskView,
+            &[(&[Action::Close], "Close"), (&[Action::ScrollDown, Action::ScrollUp], "Scroll")]
+        );
         let info = Paragraph::new(
             Span::styled(
-                "Press Enter to close, 'j' and 'k' to Scroll",
+                hints.unwrap_or_default(),
                 Style::default()
                 .fg(Color::Red)
                 .add_modifier(Modifier::BOLD)
@@ -640,9 +945,11 @@ fn render_list_popup<B: Backend>(
         )
         .split(size);
 
+    let scroll_off = app.config.scroll_off;
+    let estimate_unit = app.config.estimate_unit;
     let task_list = app.get_mut_focused_list(&app.state.clone());
     let container = CustomBorder::new()
-        .title(task_list.name.clone())
+        .title(task_list.title(estimate_unit))
         .title_style(
             Style::default()
             .fg(
@@ -664,134 +971,766 @@ fn render_list_popup<B: Backend>(
     frame.render_widget(Clear, chunks[1]); // Clear the area first
     frame.render_widget(container, chunks[1]);
 
-    let items: Vec<ListItem> = task_list
-        .tasks
-        .iter()
-        .map(|i| {
-            ListItem::new(task_spans(i, chunks[1].width - 2))
-        })
-        .collect();
-
-    let list = List::new(items)
-        .block(Block::default())
-        .highlight_style(
-            Style::default()
-            .add_modifier(Modifier::REVERSED)
-        );
+    let highlight = Style::default()
+        .add_modifier(Modifier::REVERSED);
 
     let inner_area = shrink_rect(chunks[1], 1);
 
-    frame.render_stateful_widget(list, inner_area, &mut task_list.state);
+    let tasks: Vec<usize> = (0..task_list.len()).collect();
+    app.popup_card_areas = render_tasks(frame, task_list, &tasks, inner_area, highlight, scroll_off);
 }
 
-fn render_task_editor<B: Backend>(
+fn render_archive<B: Backend>(
     frame: &mut Frame<B>,
-    app: &mut App,
-    editor_title: String,
+    app: &mut App
 ) {
     let size = frame.size();
-    let area = centered_rect(60, 40, size);
-    let area_block = Block::default()
-        .title(
-            Span::styled(
-                editor_title,
-                Style::default()
-                .add_modifier(Modifier::BOLD)
-            )
-        )
-        .title_alignment(Alignment::Center)
-        .borders(Borders::ALL)
-        .border_type(BorderType::Double);
-
-    frame.render_widget(Clear, area); // Clear the area first
-    frame.render_widget(area_block, area);
-
-    let inner_area = shrink_rect(area, 1);
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
             Constraint::Length(3),
-            Constraint::Min(3),
             Constraint::Length(3),
-            Constraint::Length(1),
-            Constraint::Length(1),
+            Constraint::Min(5),
             ]
             .as_ref()
         )
-        .split(inner_area);
+        .split(size);
 
-    for i in 0..app.task_detail_inputs.len() {
-        let input = &app.task_detail_inputs[i];
-        let field = Paragraph::new(input.clone())
-            .style(
-                if app.active_detail_input == i {
-                    Style::default().fg(Color::Red)
-                } else {
-                    Style::default()
-                }
-            )
-            .block(
-                Block::default()
-                .borders(Borders::ALL)
-                .title(input.name.clone())
-            )
-            .wrap(Wrap { trim: true });
+    render_info_bar(frame, app, chunks[0]);
 
-        frame.render_widget(field, chunks[i]);
-    }
+    let searching = matches!(app.state, AppState::ArchiveSearch(_));
+    let input = &app.archive_search_input;
+    let search = Paragraph::new(input.clone())
+        .style(
+            if searching {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            }
+        )
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(input.name.clone())
+        );
 
-    // Display the blinking cursor, wrapped appropriately
-    let i = app.active_detail_input;
-    let input = &app.task_detail_inputs[i];
-    let input_area = chunks[i];
-    let cursor_pos = get_wrapped_cursor_pos(input, input_area);
+    frame.render_widget(search, chunks[1]);
 
-    frame.set_cursor(
-        chunks[i].x + cursor_pos.0 as u16 + 1,
-        chunks[i].y + cursor_pos.1 as u16
-    );
+    if searching {
+        let cursor_pos = get_wrapped_cursor_pos(input, chunks[1]);
 
-    let info = Paragraph::new(
-        Span::styled(
-            "Press Enter to Save Changes, Esc to Exit",
+        frame.set_cursor(
+            chunks[1].x + cursor_pos.0 as u16 + 1,
+            chunks[1].y + cursor_pos.1 as u16
+        );
+    }
+
+    let grouping = match app.archive_grouping {
+        ArchiveGrouping::Week => "(By Week)",
+        ArchiveGrouping::Month => "(By Month)",
+    };
+    let container = CustomBorder::new()
+        .title(format!("{} {}", app.archive.title(app.config.estimate_unit), grouping))
+        .title_style(
             Style::default()
-            .fg(Color::Red)
+            .fg(
+                Color::Indexed(
+                    app.archive.color_index
+                )
+            )
             .add_modifier(Modifier::BOLD)
-        ))
-        .block(Block::default())
-        .wrap(Wrap { trim: true })
-        .alignment(Alignment::Center);
+        )
+        .border_style(
+            Style::default()
+            .fg(
+                Color::Indexed(
+                    app.archive.color_index
+                )
+            )
+        );
 
-    frame.render_widget(info, chunks[3]);
+    frame.render_widget(container, chunks[2]);
 
-    let info = Paragraph::new(
-        Span::styled(
-            "Press Tab to Cycle Focus, Delete to Clear Input",
-            Style::default()
-            .fg(Color::Red)
-            .add_modifier(Modifier::BOLD)
-        ))
-        .block(Block::default())
-        .wrap(Wrap { trim: true })
-        .alignment(Alignment::Center);
+    let highlight = Style::default()
+        .add_modifier(Modifier::REVERSED);
 
-    frame.render_widget(info, chunks[4]);
+    // Each group of tasks archived in the same week or month gets a header
+    let mut rows = Vec::new();
+    let mut last_label = None;
+    for i in app.get_archive_tasks() {
+        let label = app.archive_grouping.label(&app.archive.tasks[i]);
+        if last_label.as_ref() != Some(&label) {
+            rows.push(CardRow::Header(label.clone()));
+            last_label = Some(label);
+        }
+        rows.push(CardRow::Task(i));
+    }
+
+    let (offset, visible, card_areas) = render_cards(
+        frame,
+        &app.archive,
+        &rows,
+        app.archive.offset,
+        shrink_rect(chunks[2], 1),
+        highlight,
+        app.config.scroll_off
+    );
+    app.archive.offset = offset;
+    app.archive.visible = visible;
+    app.popup_card_areas = card_areas;
 }
 
-fn render_prompt<B: Backend>(
+fn render_statistics<B: Backend>(
     frame: &mut Frame<B>,
-    prompt: String,
+    app: &mut App
 ) {
     let size = frame.size();
-    let area = centered_fixed_size_rect(prompt.len() + 6, 7, size);
-    let area_block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Double);
-
-    frame.render_widget(Clear, area); // Clear the area first
-    frame.render_widget(area_block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+            Constraint::Length(3),
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+            ]
+            .as_ref()
+        )
+        .split(size);
+
+    render_info_bar(frame, app, chunks[0]);
+
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(chunks[1]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(chunks[2]);
+
+    // One week of throughput per column, up to a year
+    let weeks = cmp::min(bottom[0].width.saturating_sub(2), 52) as usize;
+    let stats = Statistics::new(&app.task_lists, &app.backlog, &app.archive, weeks);
+
+    // Show days once the slowest list takes longer than two of them
+    let longest = stats.list_times
+        .iter()
+        .filter_map(|(_, time)| *time)
+        .max()
+        .unwrap_or_default();
+    let (unit, minutes) = if longest.num_hours() >= 48 {
+        ("Days", 24 * 60)
+    } else {
+        ("Hours", 60)
+    };
+    let list_times: Vec<(String, u64)> = stats.list_times
+        .iter()
+        .map(|(name, time)| {
+            let time = time.map_or(0, |time| (time.num_minutes() + minutes / 2) / minutes);
+            (name.clone(), time as u64)
+        })
+        .collect();
+
+    render_bar_chart(frame, format!("Average {} in List", unit), &list_times, top[0]);
+    render_bar_chart(frame, "Open Tasks by Category".to_string(), &stats.categories, top[1]);
+
+    let archived: u64 = stats.throughput.iter().sum();
+    let title = format!(
+        "Archived per Week ({:.1} Average over {} Weeks)",
+        archived as f64 / cmp::max(weeks, 1) as f64,
+        weeks
+    );
+    let throughput = Sparkline::default()
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+        )
+        .style(Style::default().fg(Color::Indexed(app.archive.color_index)))
+        .data(&stats.throughput);
+
+    frame.render_widget(throughput, bottom[0]);
+
+    let heading = Style::default()
+        .fg(Color::Red)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = Vec::new();
+    for (label, times) in [
+        ("Lead Time (Created to Archived)", &stats.lead_times),
+        ("Cycle Time (First Move to Archived)", &stats.cycle_times),
+    ] {
+        lines.push(Spans::from(Span::styled(label, heading)));
+        lines.push(Spans::from(
+            match (average(times), median(times)) {
+                (Some(average), Some(median)) => format!(
+                    "Average {}, Median {}, over {}",
+                    format_days(average),
+                    format_days(median),
+                    count_tasks(times.len())
+                ),
+                _ => "No archived tasks to measure yet".to_string()
+            }
+        ));
+        lines.push(Spans::from(""));
+    }
+
+    let times = Paragraph::new(lines)
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title("Flow Times")
+        )
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(times, bottom[1]);
+}
+
+fn render_flow_charts<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App
+) {
+    let size = frame.size();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+            Constraint::Length(3),
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+            ]
+            .as_ref()
+        )
+        .split(size);
+
+    render_info_bar(frame, app, chunks[0]);
+
+    let flow = FlowHistory::new(&app.task_lists, &app.backlog, &app.archive, 90);
+    let days = flow.days();
+
+    // Stack the lists on top of the archive, so the last list sits lowest
+    // and each line is the total of its own band and every band below it
+    let mut totals = flow.archived.clone();
+    let mut bands = vec![(app.archive.name.clone(), app.archive.color_index, day_points(&totals))];
+    for (list, counts) in app.task_lists.iter().zip(&flow.list_counts).rev() {
+        for (total, count) in totals.iter_mut().zip(counts) {
+            *total += count;
+        }
+        bands.push((list.name.clone(), list.color_index, day_points(&totals)));
+    }
+    // The legend then reads top to bottom like the chart
+    bands.reverse();
+
+    let highest = totals.iter().copied().max().unwrap_or_default();
+    let datasets = bands
+        .iter()
+        .map(|(name, color_index, points)| {
+            Dataset::default()
+                .name(name.as_str())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Indexed(*color_index)))
+                .data(points)
+        })
+        .collect();
+
+    let flow_chart = Chart::new(datasets)
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Cumulative Flow (Last {} Days)", days))
+        )
+        .x_axis(date_axis(flow.start, days))
+        .y_axis(count_axis(highest))
+        .hidden_legend_constraints((Constraint::Ratio(1, 2), Constraint::Ratio(1, 1)));
+
+    frame.render_widget(flow_chart, chunks[1]);
+
+    // Tasks count as done once they reach the last list
+    let open_lists = cmp::max(flow.list_counts.len(), 2) - 1;
+    let remaining: Vec<u64> = (0..days)
+        .map(|day| {
+            flow.list_counts
+                .iter()
+                .take(open_lists)
+                .map(|counts| counts[day])
+                .sum()
+        })
+        .collect();
+    let left = remaining.last().copied().unwrap_or_default();
+
+    // The ideal line runs from what's left today down to nothing on the target
+    let today = days - 1;
+    let (title, span, ideal) = match app.burndown_target {
+        Some(target) => {
+            let target_day = (target - flow.start).num_days();
+            let ideal = if target_day > today as i64 {
+                vec![(today as f64, left as f64), (target_day as f64, 0.0)]
+            } else {
+                Vec::new()
+            };
+            let title = format!(
+                "Burndown to {} ({} Left, {} Days to Go)",
+                target.format("%Y-%m-%d"),
+                count_tasks(left as usize),
+                cmp::max(target_day - today as i64, 0)
+            );
+            (title, cmp::max(days as i64, target_day + 1) as usize, ideal)
+        },
+        None => {
+            let hint = press_hints(
+                app,
+                Mode::FlowCharts,
+                &[(&[Action::SetBurndownTarget], "Set a Target Date")]
+            );
+            let hint = hint.map(|hint| format!(", {}", hint)).unwrap_or_default();
+            (format!("Burndown ({} Left{})", count_tasks(left as usize), hint), days, Vec::new())
+        }
+    };
+
+    let actual = day_points(&remaining);
+    let burndown_chart = Chart::new(
+        vec![
+            Dataset::default()
+                .name("Remaining")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Red))
+                .data(&actual),
+            Dataset::default()
+                .name("Ideal")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::DarkGray))
+                .data(&ideal),
+        ]
+    )
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+        )
+        .x_axis(date_axis(flow.start, span))
+        .y_axis(count_axis(remaining.iter().copied().max().unwrap_or_default()));
+
+    frame.render_widget(burndown_chart, chunks[2]);
+}
+
+fn day_points(counts: &[u64]) -> Vec<(f64, f64)> {
+    counts
+        .iter()
+        .enumerate()
+        .map(|(day, count)| (day as f64, *count as f64))
+        .collect()
+}
+
+fn date_axis<'a>(start: NaiveDate, days: usize) -> Axis<'a> {
+    let last = cmp::max(days, 2) - 1;
+    let labels = [0, last / 2, last]
+        .iter()
+        .map(|day| {
+            let date = start + TimeDelta::days(*day as i64);
+            Span::raw(date.format("%m-%d").to_string())
+        })
+        .collect();
+
+    Axis::default()
+        .style(Style::default().fg(Color::DarkGray))
+        .bounds([0.0, last as f64])
+        .labels(labels)
+}
+
+fn count_axis<'a>(highest: u64) -> Axis<'a> {
+    let top = cmp::max(highest, 1);
+
+    Axis::default()
+        .style(Style::default().fg(Color::DarkGray))
+        .bounds([0.0, top as f64])
+        .labels(vec![Span::raw("0"), Span::raw(top.to_string())])
+}
+
+fn render_bar_chart<B: Backend>(
+    frame: &mut Frame<B>,
+    title: String,
+    data: &[(String, u64)],
+    area: Rect
+) {
+    let data: Vec<(&str, u64)> = data
+        .iter()
+        .map(|(label, value)| (label.as_str(), *value))
+        .collect();
+
+    // Share the width between the bars, keeping room for short labels
+    let bar_width = (area.width.saturating_sub(2) / cmp::max(data.len(), 1) as u16)
+        .saturating_sub(1)
+        .clamp(3, 12);
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+        )
+        .data(&data)
+        .bar_width(bar_width)
+        .bar_style(Style::default().fg(Color::Red))
+        .value_style(
+            Style::default()
+            .fg(Color::Black)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        );
+
+    frame.render_widget(chart, area);
+}
+
+fn render_task_editor<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App,
+    editor_title: String,
+) {
+    let size = frame.size();
+    let area = centered_rect(60, 40, size);
+    let area_block = Block::default()
+        .title(
+            Span::styled(
+                editor_title,
+                Style::default()
+                .add_modifier(Modifier::BOLD)
+            )
+        )
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+
+    frame.render_widget(Clear, area); // Clear the area first
+    frame.render_widget(area_block, area);
+
+    let inner_area = shrink_rect(area, 1);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+            Constraint::Length(3),
+            Constraint::Min(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            ]
+            .as_ref()
+        )
+        .split(inner_area);
+
+    // Category, due date, priority and estimate share the row under the
+    // description
+    let fields = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+            Constraint::Percentage(24),
+            Constraint::Percentage(28),
+            Constraint::Percentage(20),
+            Constraint::Percentage(28),
+            ]
+            .as_ref()
+        )
+        .split(chunks[2]);
+    let input_areas = [chunks[0], chunks[1], fields[0], fields[1], fields[2], fields[3]];
+
+    for (i, input) in app.task_detail_inputs.iter().enumerate() {
+        let field = Paragraph::new(input.clone())
+            .style(
+                if app.active_detail_input == i {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                }
+            )
+            .block(
+                Block::default()
+                .borders(Borders::ALL)
+                .title(input.name.clone())
+            )
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(field, input_areas[i]);
+    }
+
+    // Display the blinking cursor, wrapped appropriately
+    let i = app.active_detail_input;
+    let input = &app.task_detail_inputs[i];
+    let input_area = input_areas[i];
+    let cursor_pos = get_wrapped_cursor_pos(input, input_area);
+
+    frame.set_cursor(
+        input_area.x + cursor_pos.0 as u16 + 1,
+        input_area.y + cursor_pos.1 as u16
+    );
+
+    let info = Paragraph::new(
+        Span::styled(
+            "Press Enter to Save Changes, Esc to Exit",
+            Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        ))
+        .block(Block::default())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+
+    frame.render_widget(info, chunks[3]);
+
+    let info = Paragraph::new(
+        Span::styled(
+            "Press Tab to Cycle Focus, Delete to Clear Input",
+            Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        ))
+        .block(Block::default())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+
+    frame.render_widget(info, chunks[4]);
+}
+
+fn render_list_editor<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App,
+    editor_title: String,
+) {
+    let size = frame.size();
+    let area = centered_fixed_size_rect((size.width as f32 * 0.6) as usize, 10, size);
+    let area_block = Block::default()
+        .title(
+            Span::styled(
+                editor_title,
+                Style::default()
+                .add_modifier(Modifier::BOLD)
+            )
+        )
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+
+    frame.render_widget(Clear, area); // Clear the area first
+    frame.render_widget(area_block, area);
+
+    let inner_area = shrink_rect(area, 1);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            ]
+            .as_ref()
+        )
+        .split(inner_area);
+
+    // The WIP limit and archive age share the row under the name
+    let fields = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+            ]
+            .as_ref()
+        )
+        .split(chunks[1]);
+    let input_areas = [chunks[0], fields[0], fields[1]];
+
+    for (i, input) in app.list_detail_inputs.iter().enumerate() {
+        let field = Paragraph::new(input.clone())
+            .style(
+                if app.active_detail_input == i {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                }
+            )
+            .block(
+                Block::default()
+                .borders(Borders::ALL)
+                .title(input.name.clone())
+            )
+            .wrap(Wrap { trim: true });
+
+        frame.render_widget(field, input_areas[i]);
+    }
+
+    let i = app.active_detail_input;
+    let input = &app.list_detail_inputs[i];
+    let input_area = input_areas[i];
+    let cursor_pos = get_wrapped_cursor_pos(input, input_area);
+
+    frame.set_cursor(
+        input_area.x + cursor_pos.0 as u16 + 1,
+        input_area.y + cursor_pos.1 as u16
+    );
+
+    let info = Paragraph::new(
+        Span::styled(
+            "Press Enter to Save Changes, Esc to Exit",
+            Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        ))
+        .block(Block::default())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+
+    frame.render_widget(info, chunks[2]);
+
+    let info = Paragraph::new(
+        Span::styled(
+            "Press Tab to Cycle Focus, Leave Limit or Days Blank for None",
+            Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        ))
+        .block(Block::default())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+
+    frame.render_widget(info, chunks[3]);
+}
+
+fn render_command_line<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App
+) {
+    let size = frame.size();
+    let area = Rect::new(size.x, size.y + size.height.saturating_sub(3), size.width, 3);
+    let input = &app.command_input;
+
+    let line = Paragraph::new(format!(":{}", input.text))
+        .style(
+            Style::default().fg(Color::Red)
+        )
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .title(
+                Span::styled(
+                    "Tab to Complete, Enter to Run, Esc to Cancel",
+                    Style::default()
+                    .add_modifier(Modifier::BOLD)
+                )
+            )
+        );
+
+    frame.render_widget(Clear, area); // Clear the area first
+    frame.render_widget(line, area);
+
+    frame.set_cursor(area.x + input.pos as u16 + 2, area.y + 1);
+}
+
+fn render_picker<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App
+) {
+    let size = frame.size();
+    let matches = app.picker.get_matches();
+    let height = cmp::min(matches.len() + 7, size.height as usize - 4);
+    let area = centered_fixed_size_rect(40, height, size);
+    let area_block = Block::default()
+        .title(
+            Span::styled(
+                app.picker.title.clone(),
+                Style::default()
+                .add_modifier(Modifier::BOLD)
+            )
+        )
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+
+    frame.render_widget(Clear, area); // Clear the area first
+    frame.render_widget(area_block, area);
+
+    let inner_area = shrink_rect(area, 1);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+            ]
+            .as_ref()
+        )
+        .split(inner_area);
+
+    let input = &app.picker.input;
+    let filter = Paragraph::new(input.clone())
+        .style(
+            Style::default().fg(Color::Red)
+        )
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(input.name.clone())
+        );
+
+    frame.render_widget(filter, chunks[0]);
+
+    let cursor_pos = get_wrapped_cursor_pos(input, chunks[0]);
+    frame.set_cursor(
+        chunks[0].x + cursor_pos.0 as u16 + 1,
+        chunks[0].y + cursor_pos.1 as u16
+    );
+
+    let items: Vec<ListItem> = matches
+        .into_iter()
+        .map(|i| ListItem::new(format!(" {}", app.picker.items[i])))
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default())
+        .highlight_style(
+            Style::default()
+            .add_modifier(Modifier::REVERSED)
+        );
+
+    frame.render_stateful_widget(list, chunks[1], &mut app.picker.state);
+
+    let info = Paragraph::new(
+        Span::styled(
+            "Press Enter to Select, Esc to Cancel",
+            Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        ))
+        .block(Block::default())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+
+    frame.render_widget(info, chunks[2]);
+}
+
+fn render_prompt<B: Backend>(
+    frame: &mut Frame<B>,
+    prompt: String,
+) {
+    let size = frame.size();
+    let area = centered_fixed_size_rect(prompt.len() + 6, 7, size);
+    let area_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+
+    frame.render_widget(Clear, area); // Clear the area first
+    frame.render_widget(area_block, area);
 
     let inner_area = shrink_rect(area, 1);
 
@@ -825,14 +1764,154 @@ fn render_prompt<B: Backend>(
                 Style::default()
                 .add_modifier(Modifier::BOLD)
             )
-        ),
-        ];
-    let text = Paragraph::new(text)
+        ),
+        ];
+    let text = Paragraph::new(text)
+        .block(Block::default())
+        .wrap(Wrap { trim: true })
+        .alignment(Alignment::Center);
+
+    frame.render_widget(text, chunks[0]);
+}
+
+fn render_help<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App
+) {
+    let size = frame.size();
+    let area = centered_rect(60, 60, size);
+    let area_block = Block::default()
+        .title(
+            Span::styled(
+                "Keybindings",
+                Style::default()
+                .add_modifier(Modifier::BOLD)
+            )
+        )
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+
+    frame.render_widget(Clear, area); // Clear the area first
+    frame.render_widget(area_block, area);
+
+    let inner_area = shrink_rect(area, 1);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(1),
+            ]
+            .as_ref()
+        )
+        .split(inner_area);
+
+    let searching = matches!(app.state, AppState::HelpSearch(_));
+    let input = &app.help_search_input;
+    let search = Paragraph::new(input.clone())
+        .style(
+            if searching {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            }
+        )
+        .block(
+            Block::default()
+            .borders(Borders::ALL)
+            .title(input.name.clone())
+        );
+
+    frame.render_widget(search, chunks[0]);
+
+    if searching {
+        let cursor_pos = get_wrapped_cursor_pos(input, chunks[0]);
+
+        frame.set_cursor(
+            chunks[0].x + cursor_pos.0 as u16 + 1,
+            chunks[0].y + cursor_pos.1 as u16
+        );
+    }
+
+    let entries = app.get_help_entries();
+    let key_width = entries
+        .iter()
+        .map(|(keys, _)| keys.len())
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<Spans> = entries
+        .into_iter()
+        .map(|(keys, description)| {
+            Spans::from(
+                vec![
+                    Span::styled(
+                        format!(" {:width$}  ", keys, width = key_width),
+                        Style::default()
+                        .fg(Color::Red)
+                        .add_modifier(Modifier::BOLD)
+                    ),
+                    Span::raw(description),
+                ]
+            )
+        })
+        .collect();
+
+    let bindings = Paragraph::new(lines)
+        .block(Block::default())
+        .scroll((app.help_scroll, 0));
+
+    frame.render_widget(bindings, chunks[1]);
+
+    let hints = press_hints(
+        app,
+        Mode::Help,
+        &[
+            (&[Action::Search], "Search"),
+            (&[Action::ScrollDown, Action::ScrollUp], "Scroll"),
+            (&[Action::Close], "Close"),
+        ]
+    );
+    let info = Paragraph::new(
+        Span::styled(
+            hints.unwrap_or_default(),
+            Style::default()
+            .fg(Color::Red)
+            .add_modifier(Modifier::BOLD)
+        ))
         .block(Block::default())
         .wrap(Wrap { trim: true })
         .alignment(Alignment::Center);
 
-    frame.render_widget(text, chunks[0]);
+    frame.render_widget(info, chunks[2]);
+}
+
+fn press_hints(app: &App, mode: Mode, hints: &[(&[Action], &str)]) -> Option<String> {
+    // Like "Press '/' to Search, 'j' and 'k' to Scroll", from whichever keys
+    // are bound, leaving out anything that isn't
+    let hints: Vec<String> = hints
+        .iter()
+        .filter_map(|(actions, what)| {
+            let keys = actions
+                .iter()
+                .map(|action| {
+                    app.config.keymap
+                        .key_for(mode, *action)
+                        .map(|key| format!("'{}'", key))
+                })
+                .collect::<Option<Vec<String>>>()?;
+            Some(format!("{} to {}", keys.join(" and "), what))
+        })
+        .collect();
+
+    if hints.is_empty() {
+        None
+    } else {
+        Some(format!("Press {}", hints.join(", ")))
+    }
 }
 
 fn render_task_list<B: Backend>(
@@ -844,16 +1923,12 @@ fn render_task_list<B: Backend>(
     let highlight: Style;
     let border: Style;
 
-    let items: Vec<ListItem> = app
-        .task_lists[list_num]
-        .tasks
-        .iter()
-        .map(|i| {
-            ListItem::new(task_spans(i, chunk.width - 2))
-        })
-        .collect();
-
-    if app.active_list == list_num {
+    if app.drag_over == Some(list_num) {
+        highlight = Style::default();
+        border = Style::default()
+            .fg(Color::Indexed(app.task_lists[list_num].color_index))
+            .add_modifier(Modifier::BOLD);
+    } else if app.active_list == list_num {
         highlight = Style::default()
             .add_modifier(Modifier::REVERSED);
         border = Style::default()
@@ -864,40 +1939,477 @@ fn render_task_list<B: Backend>(
         border = Style::default();
     }
 
+    render_list_border(frame, &app.task_lists[list_num], chunk, border, app.config.estimate_unit);
+
+    let inner_area = shrink_rect(chunk, 1);
+
+    let tasks = app.get_visible_tasks(list_num);
+    let card_areas = render_tasks(
+        frame,
+        &mut app.task_lists[list_num],
+        &tasks,
+        inner_area,
+        highlight,
+        app.config.scroll_off
+    );
+    for (task_num, area) in card_areas {
+        app.card_areas.push((list_num, task_num, area));
+    }
+}
+
+fn render_swimlane_list<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App,
+    chunk: Rect,
+    list_num: usize
+) {
+    let border = if app.drag_over == Some(list_num) || app.active_list == list_num {
+        Style::default()
+            .fg(Color::Indexed(app.task_lists[list_num].color_index))
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+
+    render_list_border(frame, &app.task_lists[list_num], chunk, border, app.config.estimate_unit);
+
+    let inner_area = shrink_rect(chunk, 1);
+
+    let lanes = app.get_lanes();
+    let active_lane = app.get_active_lane();
+    let constraints: Vec<Constraint> = lanes
+        .iter()
+        .map(|_| Constraint::Ratio(1, lanes.len() as u32))
+        .collect();
+
+    let cells = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints.as_slice())
+        .split(inner_area);
+
+    // Each cell starts with a header line naming its lane. The headers go
+    // down first, since the scroll markers of a cell sit on the next header
+    for (lane, cell) in cells.iter().enumerate() {
+        let name = match &lanes[lane] {
+            Some(category) => category.clone(),
+            None => "No Category".to_string()
+        };
+        let style = if lane == active_lane {
+            Style::default()
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+        };
+
+        let header = Paragraph::new(Span::styled(name, style))
+            .block(Block::default())
+            .alignment(Alignment::Center);
+
+        frame.render_widget(header, Rect::new(cell.x, cell.y, cell.width, 1));
+        app.lane_areas.push((lane, *cell));
+    }
+
+    for (lane, cell) in cells.into_iter().enumerate() {
+        if cell.height < 2 {
+            continue;
+        }
+
+        let is_active_cell = app.active_list == list_num && lane == active_lane;
+        let highlight = if is_active_cell {
+            Style::default()
+                .add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+
+        let tasks = app.get_lane_tasks(list_num, lane);
+        let offset = app.lane_offsets
+            .get(&(list_num, lane))
+            .copied()
+            .unwrap_or(0);
+        let area = Rect::new(cell.x, cell.y + 1, cell.width, cell.height - 1);
+
+        let (offset, visible, card_areas) = render_cards(
+            frame,
+            &app.task_lists[list_num],
+            &task_rows(&tasks),
+            offset,
+            area,
+            highlight,
+            app.config.scroll_off
+        );
+        app.lane_offsets.insert((list_num, lane), offset);
+        if is_active_cell {
+            app.task_lists[list_num].visible = visible;
+        }
+
+        for (task_num, area) in card_areas {
+            app.card_areas.push((list_num, task_num, area));
+        }
+    }
+}
+
+fn render_collapsed_list<B: Backend>(
+    frame: &mut Frame<B>,
+    app: &mut App,
+    chunk: Rect,
+    list_num: usize
+) {
+    let list = &app.task_lists[list_num];
+    let color = Color::Indexed(list.color_index);
+    let border = if app.drag_over == Some(list_num) {
+        Style::default()
+            .fg(color)
+            .add_modifier(Modifier::BOLD)
+    } else if list.is_over_limit() {
+        Style::default()
+            .fg(Color::Red)
+    } else {
+        Style::default()
+    };
+
     let container = CustomBorder::new()
-        .title(app.task_lists[list_num].name.clone())
+        .title(list.count_label())
         .title_style(
             Style::default()
-            .fg(
-                Color::Indexed(
-                    app.task_lists[list_num].color_index
-                )
-            )
+            .fg(color)
         )
         .border_style(border);
 
     frame.render_widget(container, chunk);
 
+    // The name runs down the strip one character per line
+    let name: Vec<Spans> = list.name
+        .chars()
+        .map(|c| Spans::from(c.to_string()))
+        .collect();
+
+    let name = Paragraph::new(name)
+        .block(Block::default())
+        .style(
+            Style::default()
+            .fg(color)
+            .add_modifier(Modifier::BOLD)
+        )
+        .alignment(Alignment::Center);
+
+    frame.render_widget(name, shrink_rect(chunk, 1));
+}
+
+fn render_list_border<B: Backend>(
+    frame: &mut Frame<B>,
+    list: &TaskList,
+    chunk: Rect,
+    border: Style,
+    estimate_unit: EstimateUnit
+) {
+    let border = if list.is_over_limit() {
+        border.fg(Color::Red)
+    } else {
+        border
+    };
+    let container = CustomBorder::new()
+        .title(list.title(estimate_unit))
+        .title_style(
+            Style::default()
+            .fg(Color::Indexed(list.color_index))
+        )
+        .border_style(border);
+
+    frame.render_widget(container, chunk);
+}
+
+fn render_tasks<B: Backend>(
+    frame: &mut Frame<B>,
+    task_list: &mut TaskList,
+    tasks: &[usize],
+    area: Rect,
+    highlight: Style,
+    scroll_off: usize
+) -> Vec<(usize, Rect)> {
+    let (offset, visible, card_areas) = render_cards(
+        frame,
+        task_list,
+        &task_rows(tasks),
+        task_list.offset,
+        area,
+        highlight,
+        scroll_off
+    );
+    task_list.offset = offset;
+    task_list.visible = visible;
+
+    card_areas
+}
+
+fn render_cards<B: Backend>(
+    frame: &mut Frame<B>,
+    task_list: &TaskList,
+    rows: &[CardRow],
+    offset: usize,
+    area: Rect,
+    highlight: Style,
+    scroll_off: usize
+) -> (usize, usize, Vec<(usize, Rect)>) {
+    // Renders the given rows of a list, returning the new scroll offset,
+    // the number of visible cards, and where each card ended up
+    let header_style = Style::default()
+        .fg(Color::Indexed(task_list.color_index))
+        .add_modifier(Modifier::BOLD);
+
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut heights: Vec<usize> = Vec::new();
+    for row in rows {
+        let item = match row {
+            CardRow::Header(label) => {
+                ListItem::new(Span::styled(format!(" {}", label), header_style))
+            },
+            CardRow::Task(i) => {
+                let spans = task_spans(&task_list.tasks[*i], area.width, task_list.card_mode);
+                let item = ListItem::new(clip_card(spans, area.height as usize));
+                if task_list.tasks[*i].marked {
+                    item.style(Style::default().fg(Color::Yellow))
+                } else {
+                    item
+                }
+            }
+        };
+        heights.push(item.height());
+        items.push(item);
+    }
+    let rows: Vec<Option<usize>> = rows
+        .iter()
+        .map(|row| match row {
+            CardRow::Header(_) => None,
+            CardRow::Task(i) => Some(*i),
+        })
+        .collect();
+    let selected = task_list
+        .get_selected_index()
+        .and_then(|selected| rows.iter().position(|row| *row == Some(selected)));
+
+    // We track the scroll offset ourselves instead of leaving it to the List
+    // widget, so that we know exactly where each card ends up on screen
+    let (start, end) = get_visible_bounds(
+        &heights,
+        selected,
+        offset,
+        area.height as usize,
+        scroll_off
+    );
+
+    let items: Vec<ListItem> = items
+        .into_iter()
+        .take(end)
+        .skip(start)
+        .collect();
+
     let list = List::new(items)
         .block(Block::default())
         .highlight_style(highlight);
 
-    let inner_area = shrink_rect(chunk, 1);
+    let mut state = ListState::default();
+    state.select(selected.map(|i| i - start));
+
+    frame.render_stateful_widget(list, area, &mut state);
+
+    let mut card_areas = Vec::new();
+    let mut y = area.y;
+    for (row, height) in rows.iter().zip(heights).take(end).skip(start) {
+        let height = cmp::min(height as u16, area.bottom() - y);
+        if let Some(i) = row {
+            card_areas.push((*i, Rect::new(area.x, y, area.width, height)));
+        }
+        y += height;
+    }
+
+    let count_cards = |rows: &[Option<usize>]| rows.iter().flatten().count();
+    render_scroll_markers(frame, area, count_cards(&rows[..start]), count_cards(&rows[end..]));
+
+    (start, count_cards(&rows[start..end]), card_areas)
+}
+
+fn task_rows(tasks: &[usize]) -> Vec<CardRow> {
+    tasks.iter().map(|i| CardRow::Task(*i)).collect()
+}
+
+fn render_scroll_markers<B: Backend>(
+    frame: &mut Frame<B>,
+    area: Rect,
+    above: usize,
+    below: usize
+) {
+    // The markers sit on the borders just outside the list area
+    let style = Style::default()
+        .add_modifier(Modifier::BOLD);
+
+    if above > 0 && area.y > 0 {
+        let marker = Paragraph::new(
+            Span::styled(format!(" ▲ {} more ", above), style)
+        )
+        .alignment(Alignment::Right);
+
+        frame.render_widget(marker, Rect::new(area.x, area.y - 1, area.width, 1));
+    }
+
+    if below > 0 {
+        let marker = Paragraph::new(
+            Span::styled(format!(" ▼ {} more ", below), style)
+        )
+        .alignment(Alignment::Right);
+
+        frame.render_widget(marker, Rect::new(area.x, area.bottom(), area.width, 1));
+    }
+}
 
-    frame.render_stateful_widget(list, inner_area, &mut app.task_lists[list_num].state);
+fn get_visible_bounds(
+    heights: &[usize],
+    selected: Option<usize>,
+    offset: usize,
+    max_height: usize,
+    scroll_off: usize
+) -> (usize, usize) {
+    if heights.is_empty() {
+        return (0, 0);
+    }
+
+    let last = heights.len() - 1;
+    let mut start = cmp::min(offset, last);
+    let mut end = start;
+    let mut height = 0;
+
+    for item_height in heights.iter().skip(start) {
+        if height + item_height > max_height {
+            break;
+        }
+        height += item_height;
+        end += 1;
+    }
+
+    // A scroll-off bigger than half of what fits would fight itself, so we
+    // cap it there, which also means a huge value keeps the selection centered
+    let selected = cmp::min(selected.unwrap_or(0), last);
+    let scroll_off = cmp::min(scroll_off, (end - start).saturating_sub(1) / 2);
+
+    let targets = [
+        cmp::min(selected + scroll_off, last),
+        selected.saturating_sub(scroll_off),
+        selected,
+    ];
+
+    for target in targets {
+        while target >= end {
+            height += heights[end];
+            end += 1;
+            while height > max_height {
+                height -= heights[start];
+                start += 1;
+            }
+        }
+
+        while target < start {
+            start -= 1;
+            height += heights[start];
+            while height > max_height {
+                end -= 1;
+                height -= heights[end];
+            }
+        }
+    }
+
+    // Scrolling past a tall card can leave room for more below
+    while end < heights.len() && height + heights[end] <= max_height {
+        height += heights[end];
+        end += 1;
+    }
+
+    (start, end)
+}
+
+fn clip_card(mut lines: Vec<Spans>, height: usize) -> Vec<Spans> {
+    // A card taller than the whole list is cut short to fit, keeping its
+    // bottom border, so it can still be shown and scrolled past
+    if lines.len() > height {
+        let bottom = lines.pop();
+        lines.truncate(height.saturating_sub(1));
+        if height > 0 {
+            lines.extend(bottom);
+        }
+    }
+
+    lines
 }
 
-fn task_spans<'a>(task: &Task, width: u16) -> Vec<Spans<'a>> {
+fn task_spans<'a>(task: &Task, width: u16, card_mode: CardMode) -> Vec<Spans<'a>> {
     let mut lines = Vec::new();
 
+    if card_mode == CardMode::Compact {
+        create_compact_line(&mut lines, width, task);
+        return lines;
+    }
+
     create_top_line(&mut lines, width);
     create_summary_and_category_line(&mut lines, width, task);
-    create_description_lines(&mut lines, width, task);
+    create_priority_time_and_due_line(&mut lines, width, task);
+    if card_mode == CardMode::Detailed {
+        create_created_line(&mut lines, width, task);
+    }
+    create_description_lines(&mut lines, width, task, card_mode);
     create_bottom_line(&mut lines, width);
 
     lines
 }
 
+fn create_compact_line(lines: &mut Vec<Spans>, width: u16, task: &Task) {
+    // Summary Text
+    let limit = width as usize / 3 * 2;
+    let summary = shorten(&task.summary, limit, limit.saturating_sub(4));
+    let mut spans = vec![
+        Span::styled(
+            summary,
+            Style::default()
+            .add_modifier(Modifier::BOLD)
+        )
+    ];
+
+    // Category Text
+    if let Some(category) = &task.category {
+        let limit = width as usize / 3;
+        let category = shorten(category, limit, limit.saturating_sub(4));
+        spans.push(
+            Span::styled(
+                category,
+                Style::default()
+                .add_modifier(Modifier::ITALIC)
+            )
+        );
+    }
+
+    // Space Between Summary and Category
+    let current_width = spans
+        .iter()
+        .map(|span| span.width())
+        .sum::<usize>();
+    let remaining_width = cmp::max(
+        (width as usize).checked_sub(current_width)
+            .unwrap_or(1),
+        1
+    );
+    spans.insert(1, Span::raw(" ".repeat(remaining_width)));
+
+    lines.push(Spans::from(spans));
+}
+
+fn shorten(text: &str, limit: usize, keep: usize) -> String {
+    // Text reaching the limit is cut down to its first few characters
+    if text.chars().count() < limit {
+        return text.to_string();
+    }
+
+    format!("{}...", text.chars().take(keep).collect::<String>())
+}
+
 fn create_top_line(lines: &mut Vec<Spans>, width: u16) {
     let mut line = String::from(line::TOP_LEFT);
     for _ in 0..width - 2 {
@@ -912,29 +2424,19 @@ fn create_summary_and_category_line(lines: &mut Vec<Spans>, width: u16, task: &T
         .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
 
     // Summary Left Side
-    let line = String::from(
-        format!("{} ", line::VERTICAL)
-    );
+    let line = format!("{} ", line::VERTICAL);
     let mut spans = vec![Span::raw(line)];
 
     // Summary Text
-    let mut summary = task.summary.clone();
-    if task.summary.len() >= width as usize / 3 * 2 - 2 {
-        summary.truncate(width as usize / 3 * 2 - 6);
-        summary = format!("{}...", summary);
-    }
-    let line = String::from(&summary);
-    spans.push(Span::styled(line, line_style));
+    let limit = (width as usize / 3 * 2).saturating_sub(2);
+    let summary = shorten(&task.summary, limit, limit.saturating_sub(4));
+    spans.push(Span::styled(summary, line_style));
 
     // Category Text
     if let Some(category) = &task.category {
-        let mut category = category.clone();
-        if category.len() >= width as usize / 3  - 1{
-            category.truncate(width as usize / 3 - 5);
-            category = format!("{}...", category);
-        }
-        let line = String::from(&category);
-        spans.push(Span::styled(line, line_style));
+        let limit = (width as usize / 3).saturating_sub(1);
+        let category = shorten(category, limit, limit.saturating_sub(4));
+        spans.push(Span::styled(category, line_style));
     }
 
     // Space Between Summary and Category
@@ -950,23 +2452,105 @@ fn create_summary_and_category_line(lines: &mut Vec<Spans>, width: u16, task: &T
 
     let mut line = String::new();
     for _ in 0..remaining_width {
-        line.push_str(" ");
+        line.push(' ');
     }
     let index = cmp::max(2, spans.len() - 1);
     spans.insert(index, Span::styled(line, line_style));
 
     // Category Right Side
-    let line = String::from(
-        format!(" {}", line::VERTICAL)
-    );
+    let line = format!(" {}", line::VERTICAL);
     spans.push(Span::raw(line));
     lines.push(Spans::from(spans));
 }
 
-fn create_description_lines(lines: &mut Vec<Spans>, width: u16, task: &Task) {
+fn create_priority_time_and_due_line(lines: &mut Vec<Spans>, width: u16, task: &Task) {
+    if task.priority.is_none() && task.due.is_none() && task.time_sessions.is_empty() {
+        return;
+    }
+
+    // Priority Left Side
+    let mut spans = vec![Span::raw(format!("{} ", line::VERTICAL))];
+
+    // Priority Text
+    if let Some(priority) = task.priority {
+        let style = match priority {
+            Priority::High => Style::default().fg(Color::Red),
+            Priority::Medium => Style::default().fg(Color::Yellow),
+            Priority::Low => Style::default(),
+        };
+        spans.push(Span::styled(format!("{} Priority", priority), style));
+    }
+
+    // Time Spent Text, Right Before the Due Date
+    let mut due_spans = Vec::new();
+    if !task.time_sessions.is_empty() {
+        let (text, style) = if task.is_timing() {
+            (
+                format!("▶ {}", format_time_spent(task.time_spent())),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            )
+        } else {
+            (format_time_spent(task.time_spent()), Style::default())
+        };
+        due_spans.push(Span::styled(text, style));
+    }
+
+    // Due Date Text
+    if let Some(due) = task.due {
+        let style = if task.is_overdue() {
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        if !due_spans.is_empty() {
+            due_spans.push(Span::raw(" | "));
+        }
+        due_spans.push(Span::styled(format!("Due {}", due.format("%Y-%m-%d")), style));
+    }
+
+    // Space Between Priority and Due Date
+    let current_width = spans
+        .iter()
+        .chain(due_spans.iter())
+        .map(|span| span.width())
+        .sum::<usize>();
+    let remaining_width = cmp::max(
+        ((width - 2) as usize).checked_sub(current_width)
+            .unwrap_or(1),
+        1
+    );
+    spans.push(Span::raw(" ".repeat(remaining_width)));
+    spans.extend(due_spans);
+
+    // Due Date Right Side
+    spans.push(Span::raw(format!(" {}", line::VERTICAL)));
+    lines.push(Spans::from(spans));
+}
+
+fn create_created_line(lines: &mut Vec<Spans>, width: u16, task: &Task) {
+    let created = match task.created {
+        Some(created) => created,
+        None => return
+    };
+
+    let text = format!("Created {}", created.format("%Y-%m-%d"));
+    let remaining_width = (width as usize).saturating_sub(text.len() + 4);
+
+    lines.push(Spans::from(vec![
+        Span::raw(format!("{} ", line::VERTICAL)),
+        Span::raw(text),
+        Span::raw(format!("{} {}", " ".repeat(remaining_width), line::VERTICAL)),
+    ]));
+}
+
+fn create_description_lines(lines: &mut Vec<Spans>, width: u16, task: &Task, card_mode: CardMode) {
     if let Some(description) = &task.description {
         let mut wrapped = wrap(description, (width - 4) as usize);
-        if wrapped.len() > 3 {
+        if wrapped.len() > 3 && card_mode != CardMode::Detailed {
             wrapped.truncate(2);
             wrapped.push(Cow::Borrowed("..."));
         }
@@ -975,7 +2559,7 @@ fn create_description_lines(lines: &mut Vec<Spans>, width: u16, task: &Task) {
             // Description Left Side
             let mut spans = vec![
                 Span::raw(
-                    String::from(format!("{} ", line::VERTICAL))
+                    format!("{} ", line::VERTICAL)
                 )
             ];
 
@@ -991,7 +2575,7 @@ fn create_description_lines(lines: &mut Vec<Spans>, width: u16, task: &Task) {
 
             let mut line = String::new();
             for _ in 0..remaining_width {
-                line.push_str(" ");
+                line.push(' ');
             }
             line.push_str(line::VERTICAL);
             spans.push(Span::raw(line));
@@ -1042,9 +2626,9 @@ fn centered_fixed_size_rect(width: usize, height: usize, size: Rect) -> Rect {
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(size.height / 2 - height as u16 / 2),
+                Constraint::Length((size.height / 2).saturating_sub(height as u16 / 2)),
                 Constraint::Min(height as u16),
-                Constraint::Length(size.height / 2 - height as u16 / 2),
+                Constraint::Length((size.height / 2).saturating_sub(height as u16 / 2)),
             ]
             .as_ref(),
         )
@@ -1054,9 +2638,9 @@ fn centered_fixed_size_rect(width: usize, height: usize, size: Rect) -> Rect {
         .direction(Direction::Horizontal)
         .constraints(
             [
-                Constraint::Length(size.width / 2 - width as u16 / 2),
+                Constraint::Length((size.width / 2).saturating_sub(width as u16 / 2)),
                 Constraint::Min(width as u16),
-                Constraint::Length(size.width / 2 - width as u16 / 2),
+                Constraint::Length((size.width / 2).saturating_sub(width as u16 / 2)),
             ]
             .as_ref(),
         )